@@ -0,0 +1,59 @@
+//! Benchmarks of the interpreter, run with
+//! `cargo bench -p zokrates_core --features bench` on a nightly compiler.
+//!
+//! The program compares its argument to many constants, so that most of its constraints are the
+//! checks of bit decompositions, which `execute_unchecked` skips.
+
+#![feature(test)]
+
+extern crate test;
+extern crate zokrates_common;
+extern crate zokrates_core;
+extern crate zokrates_field;
+
+use std::io;
+use test::Bencher;
+use zokrates_common::Resolver;
+use zokrates_core::compile::{compile, CompilationArtifacts};
+use zokrates_core::ir::{Interpreter, Prog};
+use zokrates_field::Bn128Field;
+
+const SOURCE: &str = r#"
+def main(private field a) -> field:
+    field count = 0
+    for field i in 0..50 do
+        count = count + if a < i then 1 else 0 fi
+    endfor
+    return count
+"#;
+
+fn program() -> Prog<Bn128Field> {
+    let artifacts: CompilationArtifacts<Bn128Field> = compile(
+        SOURCE.to_string(),
+        "./bench.zok".into(),
+        None::<&dyn Resolver<io::Error>>,
+    )
+    .unwrap();
+
+    artifacts.prog().clone()
+}
+
+#[bench]
+fn execute(b: &mut Bencher) {
+    let program = program();
+    let inputs = vec![Bn128Field::from(42)];
+
+    b.iter(|| Interpreter::default().execute(&program, &inputs).unwrap());
+}
+
+#[bench]
+fn execute_unchecked(b: &mut Bencher) {
+    let program = program();
+    let inputs = vec![Bn128Field::from(42)];
+
+    b.iter(|| {
+        Interpreter::default()
+            .execute_unchecked(&program, &inputs)
+            .unwrap()
+    });
+}
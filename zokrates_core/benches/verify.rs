@@ -0,0 +1,58 @@
+//! Benchmarks of the G16 verification on BN128, run with
+//! `cargo bench -p zokrates_core --features bench` on a nightly compiler.
+//!
+//! The key and the proof are fixtures of the program `def main(field a) -> field: return a`
+//! on the input `42`, so that a change of the setup does not change what is measured.
+
+#![feature(test)]
+
+extern crate serde_json;
+extern crate test;
+extern crate zokrates_core;
+extern crate zokrates_field;
+
+use test::Bencher;
+use zokrates_core::proof_system::bellman::groth16::{
+    PreparedVerificationKey, ProofPoints, VerificationKey, G16,
+};
+use zokrates_core::proof_system::{Proof, ProofSystem};
+use zokrates_field::Bn128Field;
+
+const VERIFICATION_KEY: &str = include_str!("fixtures/verification.key.json");
+const PROOF: &str = include_str!("fixtures/proof.json");
+
+/// The fixture key and proof, checked to verify so that a regression which breaks the
+/// verification is not reported as a speedup
+fn fixture() -> (VerificationKey, Proof<ProofPoints>) {
+    let vk: VerificationKey = serde_json::from_str(VERIFICATION_KEY).unwrap();
+    let proof: Proof<ProofPoints> = serde_json::from_str(PROOF).unwrap();
+
+    assert!(<G16 as ProofSystem<Bn128Field>>::verify(
+        vk.clone(),
+        proof.clone()
+    ));
+
+    (vk, proof)
+}
+
+#[bench]
+fn verify(b: &mut Bencher) {
+    let (vk, proof) = fixture();
+
+    b.iter(|| <G16 as ProofSystem<Bn128Field>>::verify(vk.clone(), proof.clone()));
+}
+
+#[bench]
+fn verify_prepared(b: &mut Bencher) {
+    let (vk, proof) = fixture();
+    let pvk = PreparedVerificationKey::<Bn128Field>::prepare(&vk).unwrap();
+
+    b.iter(|| G16::verify_prepared(&pvk, proof.clone()).unwrap());
+}
+
+#[bench]
+fn prepare(b: &mut Bencher) {
+    let (vk, _) = fixture();
+
+    b.iter(|| PreparedVerificationKey::<Bn128Field>::prepare(&vk).unwrap());
+}
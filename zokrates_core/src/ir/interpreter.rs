@@ -1,6 +1,8 @@
 use crate::flat_absy::flat_variable::FlatVariable;
 use crate::ir::{LinComb, Prog, QuadComb, Statement, Witness};
 use ir::Directive;
+use num_bigint::BigUint;
+use rand::Rng;
 use solvers::Solver;
 use std::collections::BTreeMap;
 use std::fmt;
@@ -8,6 +10,9 @@ use zokrates_field::Field;
 
 pub type ExecutionResult<T> = Result<Witness<T>, Error>;
 
+/// The number of random inputs `Interpreter::random_satisfying_inputs` tries
+const RANDOM_INPUT_ATTEMPTS: usize = 16;
+
 impl<T: Field> Prog<T> {}
 
 pub struct Interpreter {
@@ -33,7 +38,54 @@ impl Interpreter {
 }
 
 impl Interpreter {
+    /// Picks random values for the arguments of `program` until they satisfy its constraints, and
+    /// returns them. This is meant to get a witness for tests of programs which accept most inputs,
+    /// it does not solve the constraints: `None` is returned if a few attempts all fail, which is
+    /// usually the case for programs that only accept specific inputs.
+    pub fn random_satisfying_inputs<T: Field, R: Rng>(
+        &self,
+        program: &Prog<T>,
+        rng: &mut R,
+    ) -> Option<Vec<T>> {
+        // draw more bytes than needed, so that reducing them is close to uniform
+        let mut bytes = vec![0u8; (T::modulus().bits() + 7) / 8 + 16];
+
+        (0..RANDOM_INPUT_ATTEMPTS).find_map(|_| {
+            let inputs: Vec<T> = (0..program.arguments_count())
+                .map(|_| {
+                    rng.fill_bytes(&mut bytes);
+                    T::from(BigUint::from_bytes_le(&bytes) % T::modulus())
+                })
+                .collect();
+
+            self.execute(program, &inputs).ok().map(|_| inputs)
+        })
+    }
+
     pub fn execute<T: Field>(&self, program: &Prog<T>, inputs: &Vec<T>) -> ExecutionResult<T> {
+        self.execute_checking(program, inputs, true)
+    }
+
+    /// Computes the witness like `execute`, without checking the constraints which do not assign a
+    /// variable. This saves their evaluation when the witness is checked later anyway, for instance
+    /// by the proving, but the witness of inputs which do not satisfy the program is then returned
+    /// instead of an error. Only use it for trusted programs and inputs, never to decide whether
+    /// inputs are valid. The gain is modest, as computing the assignments dominates the execution,
+    /// see the `interpreter` benchmarks.
+    pub fn execute_unchecked<T: Field>(
+        &self,
+        program: &Prog<T>,
+        inputs: &[T],
+    ) -> ExecutionResult<T> {
+        self.execute_checking(program, inputs, false)
+    }
+
+    fn execute_checking<T: Field>(
+        &self,
+        program: &Prog<T>,
+        inputs: &[T],
+        check_constraints: bool,
+    ) -> ExecutionResult<T> {
         let main = &program.main;
         self.check_inputs(&program, &inputs)?;
         let mut witness = BTreeMap::new();
@@ -49,6 +101,7 @@ impl Interpreter {
                         let val = quad.evaluate(&witness).unwrap();
                         witness.insert(lin.0.iter().next().unwrap().0.clone(), val);
                     }
+                    false if !check_constraints => {}
                     false => {
                         let lhs_value = quad.evaluate(&witness).unwrap();
                         let rhs_value = lin.evaluate(&witness).unwrap();
@@ -93,6 +146,32 @@ impl Interpreter {
         Ok(Witness(witness))
     }
 
+    /// Executes the program like `execute`, also reporting the variables which were assigned a value close to
+    /// the modulus of the field, as results from subtracting a larger value from a smaller one. Such values can
+    /// be intended, so this is a debugging aid rather than a check of correctness.
+    pub fn execute_with_range_warnings<T: Field>(
+        &self,
+        program: &Prog<T>,
+        inputs: &[T],
+    ) -> Result<(Witness<T>, Vec<RangeWarning<T>>), Error> {
+        let witness = self.execute(program, &inputs.to_vec())?;
+
+        let warnings = witness
+            .0
+            .iter()
+            .filter(|(variable, _)| {
+                **variable != FlatVariable::one() && !program.main.arguments.contains(variable)
+            })
+            .filter(|(_, value)| is_close_to_modulus(*value))
+            .map(|(variable, value)| RangeWarning {
+                variable: *variable,
+                value: value.clone(),
+            })
+            .collect();
+
+        Ok((witness, warnings))
+    }
+
     fn try_solve_out_of_range<T: Field>(d: &Directive<T>, witness: &mut BTreeMap<FlatVariable, T>) {
         use num::traits::Pow;
 
@@ -123,7 +202,7 @@ impl Interpreter {
         }
     }
 
-    fn check_inputs<T: Field, U>(&self, program: &Prog<T>, inputs: &Vec<U>) -> Result<(), Error> {
+    fn check_inputs<T: Field, U>(&self, program: &Prog<T>, inputs: &[U]) -> Result<(), Error> {
         if program.main.arguments.len() == inputs.len() {
             Ok(())
         } else {
@@ -193,6 +272,30 @@ impl Interpreter {
     }
 }
 
+/// Whether `value` is a small negative number, i.e. less than `2^(bits / 2)` away from the modulus
+fn is_close_to_modulus<T: Field>(value: &T) -> bool {
+    !value.is_zero()
+        && T::modulus() - value.to_biguint() < BigUint::from(1u32) << (T::get_required_bits() / 2)
+}
+
+/// A variable which was assigned a value close to the modulus of the field during execution
+#[derive(Debug, Clone, PartialEq)]
+pub struct RangeWarning<T> {
+    pub variable: FlatVariable,
+    pub value: T,
+}
+
+impl<T: Field> fmt::Display for RangeWarning<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} was assigned {}, which may be the result of an unintended wraparound",
+            self.variable,
+            self.value.to_compact_dec_string()
+        )
+    }
+}
+
 impl<T: Field> LinComb<T> {
     fn evaluate(&self, witness: &BTreeMap<FlatVariable, T>) -> Result<T, ()> {
         self.0
@@ -294,6 +397,138 @@ mod tests {
         }
     }
 
+    mod unchecked {
+        use super::*;
+        use crate::ir::Function;
+
+        // the sample program `main(x) -> x`, with a constraint `x * x == 1764` which does not
+        // assign a variable
+        fn program() -> Prog<Bn128Field> {
+            Prog {
+                main: Function {
+                    id: String::from("main"),
+                    arguments: vec![FlatVariable::new(0)],
+                    returns: vec![FlatVariable::public(0)],
+                    statements: vec![
+                        Statement::Constraint(
+                            FlatVariable::new(0).into(),
+                            FlatVariable::public(0).into(),
+                        ),
+                        Statement::Constraint(
+                            QuadComb::from_linear_combinations(
+                                FlatVariable::new(0).into(),
+                                FlatVariable::new(0).into(),
+                            ),
+                            LinComb::summand(1764, FlatVariable::one()),
+                        ),
+                    ],
+                },
+                private: vec![false],
+            }
+        }
+
+        #[test]
+        fn same_witness() {
+            let program = program();
+            let interpreter = Interpreter::default();
+
+            let inputs = vec![Bn128Field::from(42)];
+
+            assert_eq!(
+                interpreter.execute_unchecked(&program, &inputs),
+                interpreter.execute(&program, &inputs)
+            );
+            assert!(interpreter.execute(&program, &inputs).is_ok());
+        }
+
+        #[test]
+        fn skips_constraints() {
+            let program = program();
+            let interpreter = Interpreter::default();
+
+            let inputs = vec![Bn128Field::from(2)];
+
+            assert_eq!(
+                interpreter.execute(&program, &inputs),
+                Err(Error::UnsatisfiedConstraint {
+                    left: String::from("4"),
+                    right: String::from("1764"),
+                })
+            );
+
+            let witness = interpreter.execute_unchecked(&program, &inputs).unwrap();
+            assert_eq!(witness.return_values(), vec![Bn128Field::from(2)]);
+        }
+    }
+
+    mod range_warnings {
+        use super::*;
+        use crate::ir::Function;
+
+        fn subtraction() -> Prog<Bn128Field> {
+            Prog {
+                main: Function {
+                    id: String::from("main"),
+                    arguments: vec![FlatVariable::new(0), FlatVariable::new(1)],
+                    returns: vec![FlatVariable::public(0)],
+                    statements: vec![
+                        Statement::definition(
+                            FlatVariable::new(2),
+                            LinComb::from(FlatVariable::new(0))
+                                - LinComb::from(FlatVariable::new(1)),
+                        ),
+                        Statement::definition(FlatVariable::public(0), FlatVariable::new(2)),
+                    ],
+                },
+                private: vec![false, false],
+            }
+        }
+
+        #[test]
+        fn negative_difference() {
+            let interpreter = Interpreter::default();
+            let (witness, warnings) = interpreter
+                .execute_with_range_warnings(
+                    &subtraction(),
+                    &vec![Bn128Field::from(2), Bn128Field::from(3)],
+                )
+                .unwrap();
+
+            let minus_one = Bn128Field::from(0) - Bn128Field::from(1);
+            assert_eq!(witness.0.get(&FlatVariable::new(2)), Some(&minus_one));
+            assert_eq!(
+                warnings,
+                vec![
+                    RangeWarning {
+                        variable: FlatVariable::public(0),
+                        value: minus_one.clone()
+                    },
+                    RangeWarning {
+                        variable: FlatVariable::new(2),
+                        value: minus_one
+                    }
+                ]
+            );
+            assert_eq!(
+                warnings[1].to_string(),
+                "_2 was assigned (-1), which may be the result of an unintended wraparound"
+            );
+        }
+
+        #[test]
+        fn positive_difference() {
+            let interpreter = Interpreter::default();
+            let (_, warnings) = interpreter
+                .execute_with_range_warnings(
+                    &subtraction(),
+                    &vec![Bn128Field::from(3), Bn128Field::from(2)],
+                )
+                .unwrap();
+
+            assert_eq!(warnings, vec![]);
+        }
+    }
+
     #[test]
     fn bits_of_one() {
         let inputs = vec![Bn128Field::from(1)];
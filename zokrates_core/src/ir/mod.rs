@@ -1,6 +1,7 @@
 use crate::flat_absy::flat_parameter::FlatParameter;
 use crate::flat_absy::FlatVariable;
 use crate::solvers::Solver;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 use zokrates_field::Field;
 
@@ -15,7 +16,7 @@ pub use self::expression::QuadComb;
 pub use self::expression::{CanonicalLinComb, LinComb};
 pub use self::serialize::ProgEnum;
 
-pub use self::interpreter::{Error, ExecutionResult, Interpreter};
+pub use self::interpreter::{Error, ExecutionResult, Interpreter, RangeWarning};
 pub use self::witness::Witness;
 
 #[derive(Debug, Serialize, Deserialize, Clone, Hash)]
@@ -165,6 +166,131 @@ impl<T: Field> Prog<T> {
         self.private.len()
     }
 
+    pub fn private_input_count(&self) -> usize {
+        self.private.iter().filter(|p| **p).count()
+    }
+
+    /// Returns whether any input of this program is private. If none is, a proof reveals all inputs.
+    pub fn has_private_inputs(&self) -> bool {
+        self.private_input_count() > 0
+    }
+
+    /// Returns the variables which are assigned by a directive but do not appear in any constraint,
+    /// in the order in which they are assigned. A prover can pick any value for them, which usually
+    /// means that constraints are missing. This is a heuristic: a variable which appears in a
+    /// constraint is not reported, even if the constraints do not determine its value.
+    pub fn find_underconstrained(&self) -> Vec<FlatVariable> {
+        let constrained: HashSet<FlatVariable> = self
+            .main
+            .statements
+            .iter()
+            .filter_map(|s| match s {
+                Statement::Constraint(quad, lin) => Some(
+                    vec![&quad.left, &quad.right, lin]
+                        .into_iter()
+                        .flat_map(|l| l.clone().into_canonical().0.into_iter().map(|(v, _)| v)),
+                ),
+                Statement::Directive(..) => None,
+            })
+            .flatten()
+            .collect();
+
+        self.main
+            .statements
+            .iter()
+            .filter_map(|s| match s {
+                Statement::Directive(d) => Some(d.outputs.iter()),
+                Statement::Constraint(..) => None,
+            })
+            .flatten()
+            .filter(|v| !constrained.contains(v))
+            .cloned()
+            .collect()
+    }
+
+    pub fn public_input_layout(&self) -> PublicInputLayout {
+        PublicInputLayout {
+            variables: self
+                .main
+                .arguments
+                .iter()
+                .zip(self.private.iter())
+                .filter(|(_, private)| !**private)
+                .map(|(argument, _)| *argument)
+                .chain(self.main.returns.iter().cloned())
+                .collect(),
+        }
+    }
+
+    /// Checks that the program is well-formed: each argument has a privacy flag, the return values
+    /// are exactly `~out_0` to `~out_{n-1}`, and every variable is assigned before it is read, by
+    /// being an argument, the output of a directive or the single variable of a definition.
+    pub fn validate(&self) -> Result<(), ProgError> {
+        if self.private.len() != self.main.arguments.len() {
+            return Err(ProgError::PrivateCountMismatch {
+                arguments: self.main.arguments.len(),
+                private: self.private.len(),
+            });
+        }
+
+        let mut returns = HashSet::new();
+        for r in &self.main.returns {
+            if !r.is_output() {
+                return Err(ProgError::InvalidReturn(*r));
+            }
+            if !returns.insert(*r) {
+                return Err(ProgError::DuplicateReturn(*r));
+            }
+        }
+        if let Some(missing) = (0..returns.len())
+            .map(FlatVariable::public)
+            .find(|v| !returns.contains(v))
+        {
+            return Err(ProgError::MissingReturn(missing));
+        }
+
+        let mut defined: HashSet<FlatVariable> = self.main.arguments.iter().cloned().collect();
+        defined.insert(FlatVariable::one());
+
+        let check = |defined: &HashSet<FlatVariable>, lin: &LinComb<T>| match lin
+            .0
+            .iter()
+            .find(|(v, _)| !defined.contains(v))
+        {
+            Some((v, _)) => Err(ProgError::UndefinedVariable(*v)),
+            None => Ok(()),
+        };
+
+        for s in &self.main.statements {
+            match s {
+                Statement::Constraint(quad, lin) => {
+                    check(&defined, &quad.left)?;
+                    check(&defined, &quad.right)?;
+                    // like the interpreter, a constraint defines its right hand side if it is a
+                    // single unassigned variable
+                    match lin.0.as_slice() {
+                        [(v, c)] if *c == T::from(1) && !defined.contains(v) => {
+                            defined.insert(*v);
+                        }
+                        _ => check(&defined, lin)?,
+                    }
+                }
+                Statement::Directive(d) => {
+                    for i in &d.inputs {
+                        check(&defined, &i.left)?;
+                        check(&defined, &i.right)?;
+                    }
+                    defined.extend(d.outputs.iter().cloned());
+                }
+            }
+        }
+
+        match self.main.returns.iter().find(|r| !defined.contains(r)) {
+            Some(r) => Err(ProgError::UndefinedVariable(*r)),
+            None => Ok(()),
+        }
+    }
+
     pub fn parameters(&self) -> Vec<FlatParameter> {
         self.main
             .arguments
@@ -178,6 +304,68 @@ impl<T: Field> Prog<T> {
     }
 }
 
+/// The reason why a program is not well-formed, as found by `Prog::validate`
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProgError {
+    PrivateCountMismatch { arguments: usize, private: usize },
+    InvalidReturn(FlatVariable),
+    DuplicateReturn(FlatVariable),
+    MissingReturn(FlatVariable),
+    UndefinedVariable(FlatVariable),
+}
+
+impl fmt::Display for ProgError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ProgError::PrivateCountMismatch { arguments, private } => write!(
+                f,
+                "Program has {} arguments but {} privacy flags",
+                arguments, private
+            ),
+            ProgError::InvalidReturn(v) => write!(f, "Return value {} is not an output", v),
+            ProgError::DuplicateReturn(v) => write!(f, "Return value {} is declared twice", v),
+            ProgError::MissingReturn(v) => write!(f, "Return value {} is missing", v),
+            ProgError::UndefinedVariable(v) => {
+                write!(f, "Variable {} is used before it is assigned", v)
+            }
+        }
+    }
+}
+
+/// The public variables of a program in the order of the public inputs of its proofs, which is
+/// also the order of the `gamma_abc` points of its verification key after the first one: the
+/// public arguments, followed by the return values
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PublicInputLayout {
+    variables: Vec<FlatVariable>,
+}
+
+impl PublicInputLayout {
+    pub fn variables(&self) -> &[FlatVariable] {
+        &self.variables
+    }
+
+    pub fn len(&self) -> usize {
+        self.variables.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.variables.is_empty()
+    }
+
+    /// Orders the values of the public variables like the public inputs of a proof. Values of
+    /// other variables are ignored. Fails with the first public variable which has no value.
+    pub fn order<T: Clone>(
+        &self,
+        values: &HashMap<FlatVariable, T>,
+    ) -> Result<Vec<T>, FlatVariable> {
+        self.variables
+            .iter()
+            .map(|v| values.get(v).cloned().ok_or(*v))
+            .collect()
+    }
+}
+
 impl<T: Field> fmt::Display for Prog<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", self.main)
@@ -204,4 +392,155 @@ mod tests {
             assert_eq!(format!("{}", c), "(1 * _42) * (1 * _42) == 1 * _42")
         }
     }
+
+    mod prog {
+        use super::*;
+
+        fn prog(private: Vec<bool>) -> Prog<Bn128Field> {
+            Prog {
+                main: Function {
+                    id: String::from("main"),
+                    arguments: (0..private.len()).map(FlatVariable::new).collect(),
+                    returns: vec![],
+                    statements: vec![],
+                },
+                private,
+            }
+        }
+
+        #[test]
+        fn all_public_inputs() {
+            let p = prog(vec![false, false]);
+            assert_eq!(p.private_input_count(), 0);
+            assert!(!p.has_private_inputs());
+        }
+
+        #[test]
+        fn public_input_layout() {
+            let mut p = prog(vec![true, false, true, false]);
+            p.main.returns = vec![FlatVariable::public(0), FlatVariable::public(1)];
+
+            let layout = p.public_input_layout();
+            assert_eq!(
+                layout.variables(),
+                &[
+                    FlatVariable::new(1),
+                    FlatVariable::new(3),
+                    FlatVariable::public(0),
+                    FlatVariable::public(1)
+                ]
+            );
+
+            let values: HashMap<_, _> = vec![
+                (FlatVariable::public(1), 4),
+                (FlatVariable::new(0), 0),
+                (FlatVariable::public(0), 3),
+                (FlatVariable::new(3), 2),
+                (FlatVariable::new(1), 1),
+            ]
+            .into_iter()
+            .collect();
+            assert_eq!(layout.order(&values), Ok(vec![1, 2, 3, 4]));
+
+            let mut missing = values;
+            missing.remove(&FlatVariable::new(3));
+            assert_eq!(layout.order(&missing), Err(FlatVariable::new(3)));
+        }
+
+        #[test]
+        fn some_private_inputs() {
+            let p = prog(vec![true, false, true]);
+            assert_eq!(p.private_input_count(), 2);
+            assert!(p.has_private_inputs());
+        }
+
+        fn div_prog(constrain_quotient: bool) -> Prog<Bn128Field> {
+            // _2 = _0 / _1, computed by a directive
+            let mut statements = vec![Statement::Directive(Directive {
+                inputs: vec![FlatVariable::new(0).into(), FlatVariable::new(1).into()],
+                outputs: vec![FlatVariable::new(2)],
+                solver: Solver::Div,
+            })];
+            if constrain_quotient {
+                // _2 * _1 == _0
+                statements.push(Statement::constraint(
+                    QuadComb::from_linear_combinations(
+                        FlatVariable::new(2).into(),
+                        FlatVariable::new(1).into(),
+                    ),
+                    FlatVariable::new(0),
+                ));
+            }
+            statements.push(Statement::definition(
+                FlatVariable::public(0),
+                LinComb::from(FlatVariable::new(0)),
+            ));
+
+            Prog {
+                main: Function {
+                    id: String::from("main"),
+                    arguments: vec![FlatVariable::new(0), FlatVariable::new(1)],
+                    returns: vec![FlatVariable::public(0)],
+                    statements,
+                },
+                private: vec![true, true],
+            }
+        }
+
+        #[test]
+        fn underconstrained() {
+            assert_eq!(
+                div_prog(false).find_underconstrained(),
+                vec![FlatVariable::new(2)]
+            );
+        }
+
+        #[test]
+        fn well_constrained() {
+            assert_eq!(div_prog(true).find_underconstrained(), vec![]);
+        }
+
+        #[test]
+        fn validate() {
+            assert_eq!(div_prog(true).validate(), Ok(()));
+            assert_eq!(prog(vec![true, false]).validate(), Ok(()));
+        }
+
+        #[test]
+        fn validate_duplicate_return() {
+            let mut p = div_prog(true);
+            p.main.returns = vec![FlatVariable::public(0), FlatVariable::public(0)];
+            assert_eq!(
+                p.validate(),
+                Err(ProgError::DuplicateReturn(FlatVariable::public(0)))
+            );
+        }
+
+        #[test]
+        fn validate_malformed() {
+            let mut p = div_prog(true);
+            p.private.pop();
+            assert_eq!(
+                p.validate(),
+                Err(ProgError::PrivateCountMismatch {
+                    arguments: 2,
+                    private: 1
+                })
+            );
+
+            let mut p = div_prog(true);
+            p.main.returns = vec![FlatVariable::public(1)];
+            assert_eq!(
+                p.validate(),
+                Err(ProgError::MissingReturn(FlatVariable::public(0)))
+            );
+
+            let mut p = div_prog(true);
+            p.main.statements.remove(0);
+            assert_eq!(
+                p.validate(),
+                Err(ProgError::UndefinedVariable(FlatVariable::new(2)))
+            );
+        }
+    }
 }
@@ -20,41 +20,58 @@ impl<T: Field> Prog<T> {
 
         serialize_into(&mut w, self, Infinite).unwrap();
     }
+
+    /// Reads a program written by `serialize`, failing if it was compiled for another curve than
+    /// the one of `T` or if it cannot be decoded
+    pub fn read<R: Read>(mut r: R) -> Result<Self, String> {
+        let curve = read_header(&mut r)?;
+
+        if curve != T::id() {
+            return Err(format!("Expected a program for curve {}", T::name()));
+        }
+
+        deserialize_from(&mut r, Infinite).map_err(|e| format!("Cannot read program: {}", e))
+    }
+}
+
+/// Checks the magic number and the version, and returns the curve identifier
+fn read_header<R: Read>(r: &mut R) -> Result<[u8; 4], String> {
+    // Check the magic number, `ZOK`
+    let mut magic = [0; 4];
+    r.read_exact(&mut magic)
+        .map_err(|_| String::from("Cannot read magic number"))?;
+
+    if &magic == ZOKRATES_MAGIC {
+        // Check the version, 1
+        let mut version = [0; 4];
+        r.read_exact(&mut version)
+            .map_err(|_| String::from("Cannot read version"))?;
+
+        if &version == ZOKRATES_VERSION_1 {
+            let mut curve = [0; 4];
+            r.read_exact(&mut curve)
+                .map_err(|_| String::from("Cannot read curve identifier"))?;
+
+            Ok(curve)
+        } else {
+            Err(String::from("Unknown version"))
+        }
+    } else {
+        Err(String::from("Wrong magic number"))
+    }
 }
 
 impl ProgEnum {
     pub fn deserialize<R: Read>(mut r: R) -> Result<Self, String> {
-        // Check the magic number, `ZOK`
-        let mut magic = [0; 4];
-        r.read_exact(&mut magic)
-            .map_err(|_| String::from("Cannot read magic number"))?;
-
-        if &magic == ZOKRATES_MAGIC {
-            // Check the version, 1
-            let mut version = [0; 4];
-            r.read_exact(&mut version)
-                .map_err(|_| String::from("Cannot read version"))?;
-
-            if &version == ZOKRATES_VERSION_1 {
-                // Check the curve identifier, deserializing accordingly
-                let mut curve = [0; 4];
-                r.read_exact(&mut curve)
-                    .map_err(|_| String::from("Cannot read curve identifier"))?;
-
-                match curve {
-                    m if m == Bls12Field::id() => Ok(ProgEnum::Bls12Program(
-                        deserialize_from(&mut r, Infinite).unwrap(),
-                    )),
-                    m if m == Bn128Field::id() => Ok(ProgEnum::Bn128Program(
-                        deserialize_from(&mut r, Infinite).unwrap(),
-                    )),
-                    _ => Err(String::from("Unknown curve identifier")),
-                }
-            } else {
-                Err(String::from("Unknown version"))
-            }
-        } else {
-            Err(String::from("Wrong magic number"))
+        // Check the curve identifier, deserializing accordingly
+        match read_header(&mut r)? {
+            m if m == Bls12Field::id() => Ok(ProgEnum::Bls12Program(
+                deserialize_from(&mut r, Infinite).unwrap(),
+            )),
+            m if m == Bn128Field::id() => Ok(ProgEnum::Bn128Program(
+                deserialize_from(&mut r, Infinite).unwrap(),
+            )),
+            _ => Err(String::from("Unknown curve identifier")),
         }
     }
 }
@@ -110,4 +127,41 @@ mod tests {
 
         assert_eq!(ProgEnum::Bls12Program(p), deserialized_p);
     }
+
+    #[test]
+    fn read() {
+        use flat_absy::FlatVariable;
+        use ir::Interpreter;
+
+        let p: ir::Prog<Bn128Field> = ir::Prog {
+            main: ir::Function {
+                id: String::from("main"),
+                arguments: vec![FlatVariable::new(0)],
+                returns: vec![FlatVariable::public(0)],
+                statements: vec![ir::Statement::Constraint(
+                    FlatVariable::new(0).into(),
+                    FlatVariable::public(0).into(),
+                )],
+            },
+            private: vec![false],
+        };
+
+        let mut buffer = vec![];
+        p.serialize(&mut buffer);
+
+        let read = ir::Prog::<Bn128Field>::read(&buffer[..]).unwrap();
+        assert_eq!(read, p);
+
+        let interpreter = Interpreter::default();
+        assert_eq!(
+            interpreter.execute(&read, &vec![Bn128Field::from(42)]),
+            interpreter.execute(&p, &vec![Bn128Field::from(42)])
+        );
+
+        assert_eq!(
+            ir::Prog::<Bls12Field>::read(&buffer[..]),
+            Err(String::from("Expected a program for curve bls12_381"))
+        );
+        assert!(ir::Prog::<Bn128Field>::read(&buffer[..buffer.len() - 1]).is_err());
+    }
 }
@@ -1,4 +1,5 @@
 use crate::flat_absy::FlatVariable;
+use crate::ir::Prog;
 use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 use std::io;
@@ -33,6 +34,33 @@ impl<T: Field> Witness<T> {
             .join("\n")
     }
 
+    /// Applies `f` to the values of `variables`, in order, for instance to derive the inputs of
+    /// another program from this witness. Returns `None` if a variable is not part of the witness.
+    pub fn compute<F: Fn(&[T]) -> T>(&self, variables: &[FlatVariable], f: F) -> Option<T> {
+        let values = variables
+            .iter()
+            .map(|v| self.0.get(v).cloned())
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(f(&values))
+    }
+
+    /// Returns the public inputs of a proof of `prog` for this witness, in the order and the
+    /// `0x`-prefixed, zero-padded hex encoding of `Proof::inputs`: the public arguments, followed
+    /// by the return values. Returns `None` if a public input is not part of the witness, which is
+    /// the case if the witness does not belong to `prog`.
+    pub fn to_public_inputs(&self, prog: &Prog<T>) -> Option<Vec<String>> {
+        prog.public_input_layout()
+            .variables()
+            .iter()
+            .map(|v| {
+                self.0
+                    .get(v)
+                    .map(|value| format!("0x{:064x}", value.to_biguint()))
+            })
+            .collect()
+    }
+
     pub fn empty() -> Self {
         Witness(BTreeMap::new())
     }
@@ -108,6 +136,73 @@ mod tests {
     use super::*;
     use zokrates_field::Bn128Field;
 
+    #[test]
+    fn compute() {
+        let w = Witness(
+            vec![
+                (FlatVariable::new(0), Bn128Field::from(42)),
+                (FlatVariable::public(0), Bn128Field::from(3)),
+            ]
+            .into_iter()
+            .collect(),
+        );
+
+        let inverse = w
+            .compute(&[FlatVariable::new(0)], |v| v[0].inverse().unwrap())
+            .unwrap();
+        assert_eq!(Bn128Field::from(42) * inverse, Bn128Field::from(1));
+
+        assert_eq!(
+            w.compute(&[FlatVariable::new(0), FlatVariable::public(0)], |v| {
+                v[0].clone() * &v[1] - &v[1].pow_u64(2)
+            }),
+            Some(Bn128Field::from(117))
+        );
+
+        assert_eq!(w.compute(&[FlatVariable::new(1)], |v| v[0].clone()), None);
+    }
+
+    #[test]
+    fn to_public_inputs() {
+        use crate::ir::{Function, Interpreter, Statement};
+
+        let program = |private: bool| Prog {
+            main: Function {
+                id: String::from("main"),
+                arguments: vec![FlatVariable::new(0)],
+                returns: vec![FlatVariable::public(0)],
+                statements: vec![Statement::Constraint(
+                    FlatVariable::new(0).into(),
+                    FlatVariable::public(0).into(),
+                )],
+            },
+            private: vec![private],
+        };
+
+        let forty_two = "0x000000000000000000000000000000000000000000000000000000000000002a";
+
+        let private = program(true);
+        let witness = Interpreter::default()
+            .execute(&private, &vec![Bn128Field::from(42)])
+            .unwrap();
+        assert_eq!(
+            witness.to_public_inputs(&private),
+            Some(vec![forty_two.to_string()])
+        );
+
+        let public = program(false);
+        let witness = Interpreter::default()
+            .execute(&public, &vec![Bn128Field::from(42)])
+            .unwrap();
+        assert_eq!(
+            witness.to_public_inputs(&public),
+            Some(vec![forty_two.to_string(), forty_two.to_string()])
+        );
+
+        // the witness of another program
+        assert_eq!(Witness::empty().to_public_inputs(&public), None);
+    }
+
     mod io {
         use super::*;
         use std::io::Cursor;
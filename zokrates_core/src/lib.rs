@@ -15,7 +15,9 @@ extern crate ff_ce as ff;
 extern crate hex;
 extern crate lazy_static;
 extern crate pairing_ce as pairing;
+extern crate rand;
 extern crate regex;
+extern crate sha3;
 extern crate zokrates_common;
 extern crate zokrates_field;
 extern crate zokrates_pest_ast;
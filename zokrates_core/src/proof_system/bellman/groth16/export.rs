@@ -0,0 +1,2520 @@
+use num_bigint::BigUint;
+use regex::{Captures, Regex};
+use std::fmt;
+
+use super::{Error, ProofPoints, VerificationKey, G16};
+use crate::proof_system::solidity::{
+    fill, placeholder, SOLIDITY_G2_ADDITION_LIB, SOLIDITY_PAIRING_LIB, SOLIDITY_PAIRING_LIB_V2,
+};
+#[cfg(feature = "cairo")]
+use proof_system::{G1Affine, G2Affine};
+use proof_system::{Proof, SolidityAbi};
+use zokrates_field::{Bn128Field, Field};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Visibility {
+    External,
+    Public,
+    Internal,
+    Private,
+}
+
+impl fmt::Display for Visibility {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Visibility::External => write!(f, "external"),
+            Visibility::Public => write!(f, "public"),
+            Visibility::Internal => write!(f, "internal"),
+            Visibility::Private => write!(f, "private"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mutability {
+    Pure,
+    View,
+    /// no mutability keyword
+    NonPayable,
+    Payable,
+}
+
+impl fmt::Display for Mutability {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Mutability::Pure => write!(f, "pure"),
+            Mutability::View => write!(f, "view"),
+            Mutability::NonPayable => write!(f, ""),
+            Mutability::Payable => write!(f, "payable"),
+        }
+    }
+}
+
+fn modifiers(visibility: Visibility, mutability: Mutability) -> String {
+    match mutability {
+        Mutability::NonPayable => visibility.to_string(),
+        _ => format!("{} {}", visibility, mutability),
+    }
+}
+
+/// Options for `G16::export_solidity_verifier_with_options`.
+/// The default generates the same contract as `ProofSystem::export_solidity_verifier`.
+#[derive(Debug, Clone)]
+pub struct SolidityExportOptions {
+    pub verify_visibility: Visibility,
+    pub verify_mutability: Mutability,
+    pub verify_tx_visibility: Visibility,
+    pub verify_tx_mutability: Mutability,
+    /// Emit the `gamma_abc` points as a single `uint256` array literal which is unpacked in a loop,
+    /// instead of one assignment per point
+    pub pack_gamma_abc: bool,
+    /// Declare the coordinates of the `gamma_abc` points as contract constants named
+    /// `VK_GAMMA_ABC_<i>_X` and `VK_GAMMA_ABC_<i>_Y`, which the key refers to, so that the
+    /// verifiers of two versions of a circuit can be compared point by point
+    pub name_gamma_abc: bool,
+    /// The name of the verifier contract, which must match `^[A-Za-z_$][A-Za-z0-9_$]*$`
+    pub contract_name: String,
+    /// If set, the source starts with an `SPDX-License-Identifier` comment with this value, which
+    /// must be an SPDX license expression
+    pub spdx_license_identifier: Option<String>,
+    /// The addresses of the precompiles called by the pairing library
+    pub precompile_addresses: PrecompileAddresses,
+    /// If set, the verifier also has a `verifyTxWithPreimage` function which takes the preimage of
+    /// the single public input as `bytes`, and hashes it to a field element with this function
+    pub public_input_hash: Option<PublicInputHash>,
+    /// Emit the verifier as a `library` named `contract_name`, with only the internal `verifyingKey`
+    /// and `verify` functions, so that other contracts can verify proofs without calling a deployed
+    /// verifier. `verify` is then always internal, and `verifyTx` is omitted along with the options
+    /// which only affect it: `verify_tx_*`, `emit_verified_event`, `public_input_hash` and
+    /// `input_names`
+    pub library: bool,
+    /// Emit `event ProofVerified(uint[] input)` with the public inputs when `verifyTx` succeeds.
+    /// Emitting an event modifies the state, so a `pure` or `view` `verify_tx_mutability` is then
+    /// replaced by non-payable
+    pub emit_verified_event: bool,
+    /// Add a `vkHash` function returning `VerificationKey::keccak256_commitment` of the key, so
+    /// that anyone can check which key a deployed verifier uses
+    pub vk_hash: bool,
+    /// If set, `verifyTx` takes one `bytes32` parameter per public input with these names, in
+    /// order, instead of a single `uint` array. Each value is converted to `uint256` and must be
+    /// an element of the scalar field for the verification to succeed. The names must be distinct
+    /// identifiers, other than the names `verifyTx` already uses, see `RESERVED_INPUT_NAMES`.
+    pub input_names: Option<Vec<String>>,
+    /// Give the checks of the public inputs in `verify` revert reasons, so that a failed call tells
+    /// whether the number of inputs or the range of an input is wrong
+    pub require_messages: bool,
+    /// Declare the arguments of `verifyTx` in `calldata` instead of `memory`, which saves copying
+    /// them. Only applies if `verify_tx_visibility` is `External`, as other functions cannot take
+    /// `calldata` arguments in the supported Solidity versions
+    pub calldata_arguments: bool,
+}
+
+impl Default for SolidityExportOptions {
+    fn default() -> Self {
+        SolidityExportOptions {
+            verify_visibility: Visibility::Internal,
+            verify_mutability: Mutability::View,
+            verify_tx_visibility: Visibility::Public,
+            verify_tx_mutability: Mutability::View,
+            pack_gamma_abc: false,
+            name_gamma_abc: false,
+            contract_name: String::from("Verifier"),
+            spdx_license_identifier: None,
+            emit_verified_event: false,
+            precompile_addresses: PrecompileAddresses::default(),
+            public_input_hash: None,
+            library: false,
+            vk_hash: false,
+            input_names: None,
+            require_messages: false,
+            calldata_arguments: false,
+        }
+    }
+}
+
+/// How `verifyTxWithPreimage` hashes its preimage to the public input, which is the hash
+/// interpreted as a big endian integer and reduced modulo the scalar field
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PublicInputHash {
+    Keccak256,
+    Sha256,
+}
+
+impl PublicInputHash {
+    fn solidity_function(&self) -> &'static str {
+        match self {
+            PublicInputHash::Keccak256 => "keccak256",
+            PublicInputHash::Sha256 => "sha256",
+        }
+    }
+}
+
+/// The addresses of the alt_bn128 precompiles, which some chains expose at other addresses than Ethereum
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PrecompileAddresses {
+    /// G1 point addition, `0x6` on Ethereum
+    pub ec_add: u64,
+    /// G1 scalar multiplication, `0x7` on Ethereum
+    pub ec_mul: u64,
+    /// pairing check, `0x8` on Ethereum
+    pub ec_pairing: u64,
+}
+
+impl Default for PrecompileAddresses {
+    fn default() -> Self {
+        PrecompileAddresses {
+            ec_add: 6,
+            ec_mul: 7,
+            ec_pairing: 8,
+        }
+    }
+}
+
+/// The names of the parameters and variables of `verifyTx`, which cannot name public inputs
+pub const RESERVED_INPUT_NAMES: &[&str] = &["proof", "a", "b", "c", "input", "inputValues"];
+
+/// Returns whether `name` is a Solidity identifier
+fn is_identifier(name: &str) -> bool {
+    use lazy_static::lazy_static;
+
+    lazy_static! {
+        static ref IDENTIFIER: Regex = Regex::new(r"^[A-Za-z_$][A-Za-z0-9_$]*$").unwrap();
+    }
+
+    IDENTIFIER.is_match(name)
+}
+
+/// Returns whether `identifier` only holds the characters of an SPDX license expression, such as
+/// `LGPL-3.0-only AND MIT` or `(MIT OR Apache-2.0)`, so that it cannot end the license comment
+fn is_license_expression(identifier: &str) -> bool {
+    use lazy_static::lazy_static;
+
+    lazy_static! {
+        static ref LICENSE_EXPRESSION: Regex = Regex::new(r"^[A-Za-z0-9.+:() -]+$").unwrap();
+    }
+
+    LICENSE_EXPRESSION.is_match(identifier)
+}
+
+/// Checks that the names of `SolidityExportOptions::input_names` can be parameters of `verifyTx`
+fn check_input_names(names: &[String]) -> Result<(), Error> {
+    let invalid = |name: &str, reason: &str| Error::InvalidInputName {
+        name: name.to_string(),
+        reason: reason.to_string(),
+    };
+
+    for (i, name) in names.iter().enumerate() {
+        if !is_identifier(name) {
+            return Err(invalid(name, "expected a Solidity identifier"));
+        }
+        if RESERVED_INPUT_NAMES.contains(&name.as_str()) {
+            return Err(invalid(name, "already used by `verifyTx`"));
+        }
+        if names[..i].contains(name) {
+            return Err(invalid(name, "used twice"));
+        }
+    }
+
+    Ok(())
+}
+
+/// The `verifyTxWithPreimage` function of a verifier with a single public input, which computes
+/// that input from `preimage` and forwards the proof to `verifyTx`
+fn public_input_hash_wrapper(
+    abi: &SolidityAbi,
+    hash: PublicInputHash,
+    visibility: Visibility,
+    mutability: Mutability,
+    named_input: bool,
+) -> String {
+    let (proof_arguments, proof_values) = match abi {
+        SolidityAbi::V1 => (
+            "uint[2] memory a,\n            uint[2][2] memory b,\n            uint[2] memory c",
+            "a, b, c",
+        ),
+        SolidityAbi::V2 => ("Proof memory proof", "proof"),
+    };
+
+    // an external `verifyTx` cannot be called internally, and the external call through `this`
+    // reads the state, which a `pure` function cannot do
+    let (verify_tx, mutability) = match (visibility, mutability) {
+        (Visibility::External, Mutability::Pure) => ("this.verifyTx", Mutability::View),
+        (Visibility::External, mutability) => ("this.verifyTx", mutability),
+        (_, mutability) => ("verifyTx", mutability),
+    };
+
+    let input = if named_input {
+        "bytes32(input[0])"
+    } else {
+        "input"
+    };
+
+    format!(
+        r#"
+    function verifyTxWithPreimage(
+            {},
+            bytes memory preimage
+        ) {} returns (bool r) {{
+        uint256 snark_scalar_field = <%scalar_field_modulus%>;
+        uint[1] memory input;
+        input[0] = uint256({}(preimage)) % snark_scalar_field;
+        return {}({}, {});
+    }}"#,
+        proof_arguments,
+        modifiers(visibility, mutability),
+        hash.solidity_function(),
+        verify_tx,
+        proof_values,
+        input
+    )
+}
+
+/// The modulus of the scalar field of BN128, the curve of the precompiles the verifiers call
+fn scalar_field_modulus() -> BigUint {
+    Bn128Field::curve_params().scalar_modulus
+}
+
+/// Replaces the addresses of the alt_bn128 precompile calls in a pairing library
+fn with_precompile_addresses(library: &str, addresses: &PrecompileAddresses) -> String {
+    let precompile_call =
+        Regex::new(r"(?P<call>call\(sub\(gas\(\), 2000\), )(?P<address>[678]),").unwrap();
+
+    precompile_call
+        .replace_all(library, |caps: &Captures| {
+            let address = match &caps["address"] {
+                "6" => addresses.ec_add,
+                "7" => addresses.ec_mul,
+                _ => addresses.ec_pairing,
+            };
+            format!("{}{},", &caps["call"], address)
+        })
+        .into_owned()
+}
+
+/// The license of the exported verifier, which includes libraries under LGPL3 and MIT
+const SPDX_LICENSE_IDENTIFIER: &str = "LGPL-3.0-only AND MIT";
+
+/// A verifier source file, ready to be added to a Solidity project
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolidityArtifact {
+    /// `<contract name>.sol`
+    pub filename: String,
+    pub source: String,
+}
+
+impl G16 {
+    /// Exports the verifier like `try_export_solidity_verifier_with_options`, panicking if `vk`
+    /// cannot be exported
+    pub fn export_solidity_verifier_with_options(
+        vk: VerificationKey,
+        abi: SolidityAbi,
+        options: &SolidityExportOptions,
+    ) -> String {
+        G16::try_export_solidity_verifier_with_options(vk, abi, options)
+            .unwrap_or_else(|e| panic!("Cannot export verifier: {}", e))
+    }
+
+    /// Exports the verifier for `vk`, failing if `vk` has no `gamma_abc` point, as there is then
+    /// no constant term for the public inputs, if the contract name is not an identifier or if the
+    /// license identifier is not an SPDX license expression
+    pub fn try_export_solidity_verifier_with_options(
+        vk: VerificationKey,
+        abi: SolidityAbi,
+        options: &SolidityExportOptions,
+    ) -> Result<String, Error> {
+        if !is_identifier(&options.contract_name) {
+            return Err(Error::InvalidContractName(options.contract_name.clone()));
+        }
+        if let Some(identifier) = &options.spdx_license_identifier {
+            if !is_license_expression(identifier) {
+                return Err(Error::InvalidLicenseIdentifier(identifier.clone()));
+            }
+        }
+
+        let gamma_abc_count: usize = vk.gamma_abc.len();
+        let input_count = gamma_abc_count
+            .checked_sub(1)
+            .ok_or_else(|| Error::MissingPoint(String::from("gamma_abc[0]")))?;
+
+        let (mut template_text, solidity_pairing_lib) = match abi {
+            SolidityAbi::V1 => (
+                String::from(CONTRACT_TEMPLATE),
+                String::from(SOLIDITY_PAIRING_LIB),
+            ),
+            SolidityAbi::V2 => (
+                String::from(CONTRACT_TEMPLATE_V2),
+                String::from(SOLIDITY_PAIRING_LIB_V2),
+            ),
+        };
+
+        if options.library {
+            // `verifyTx` is the last function of the contract
+            let verify_tx = template_text.find("    function verifyTx(").unwrap();
+            template_text = format!("{}}}\n", &template_text[..verify_tx]).replacen(
+                "contract <%contract_name%>",
+                "library <%contract_name%>",
+                1,
+            );
+        }
+
+        let (input_count_message, input_range_message) = if options.require_messages {
+            (
+                r#", "ZoKrates: wrong number of public inputs""#,
+                r#", "ZoKrates: input out of field range""#,
+            )
+        } else {
+            ("", "")
+        };
+
+        template_text = fill(
+            &template_text,
+            placeholder::INPUT_COUNT_MESSAGE,
+            input_count_message,
+        );
+        template_text = fill(
+            &template_text,
+            placeholder::INPUT_RANGE_MESSAGE,
+            input_range_message,
+        );
+
+        let verifying_key_hash = if options.vk_hash {
+            format!(
+                "\n    function vkHash() {} pure returns (bytes32) {{\n        return 0x{};\n    }}",
+                if options.library { "internal" } else { "public" },
+                hex::encode(vk.keccak256_commitment())
+            )
+        } else {
+            String::new()
+        };
+
+        template_text = fill(
+            &template_text,
+            placeholder::CONTRACT_NAME,
+            &options.contract_name,
+        );
+
+        let verify_visibility = if options.library {
+            Visibility::Internal
+        } else {
+            options.verify_visibility
+        };
+
+        template_text = fill(
+            &template_text,
+            placeholder::VERIFY_MODIFIERS,
+            &modifiers(verify_visibility, options.verify_mutability),
+        );
+
+        let verify_tx_mutability = match options.verify_tx_mutability {
+            Mutability::Pure | Mutability::View if options.emit_verified_event => {
+                Mutability::NonPayable
+            }
+            mutability => mutability,
+        };
+
+        template_text = fill(
+            &template_text,
+            placeholder::VERIFY_TX_MODIFIERS,
+            &modifiers(options.verify_tx_visibility, verify_tx_mutability),
+        );
+
+        let (verified_event_declaration, verified_event_emit) =
+            if options.emit_verified_event && !options.library {
+                (
+                    "\n    event ProofVerified(uint[] input);",
+                    "\n            emit ProofVerified(inputValues);",
+                )
+            } else {
+                ("", "")
+            };
+
+        template_text = fill(
+            &template_text,
+            placeholder::VERIFIED_EVENT_DECLARATION,
+            verified_event_declaration,
+        );
+
+        template_text = fill(
+            &template_text,
+            placeholder::VERIFIED_EVENT_EMIT,
+            verified_event_emit,
+        );
+
+        let public_input_hash_wrapper = match options.public_input_hash {
+            Some(hash) if !options.library => {
+                if input_count != 1 {
+                    return Err(Error::InputCountMismatch {
+                        expected: 1,
+                        found: input_count,
+                    });
+                }
+                public_input_hash_wrapper(
+                    &abi,
+                    hash,
+                    options.verify_tx_visibility,
+                    verify_tx_mutability,
+                    options.input_names.is_some(),
+                )
+            }
+            _ => String::new(),
+        };
+
+        template_text = fill(
+            &template_text,
+            placeholder::PUBLIC_INPUT_HASH_WRAPPER,
+            &public_input_hash_wrapper,
+        );
+
+        // the template and the wrapper both reduce inputs modulo the scalar field
+        template_text = fill(
+            &template_text,
+            placeholder::SCALAR_FIELD_MODULUS,
+            &scalar_field_modulus().to_string(),
+        );
+
+        let (gamma_abc_constants, gamma_abc) = if options.name_gamma_abc {
+            let names = |i: usize| {
+                (
+                    format!("VK_GAMMA_ABC_{}_X", i),
+                    format!("VK_GAMMA_ABC_{}_Y", i),
+                )
+            };
+
+            let constants = vk
+                .gamma_abc
+                .iter()
+                .enumerate()
+                .map(|(i, g1)| {
+                    let (x, y) = names(i);
+                    format!(
+                        "\n    uint256 constant {} = {};\n    uint256 constant {} = {};",
+                        x, g1.0, y, g1.1
+                    )
+                })
+                .collect::<String>();
+
+            // the key refers to the constants instead of holding the coordinates
+            (constants, (0..vk.gamma_abc.len()).map(names).collect())
+        } else {
+            (String::new(), gamma_abc_coordinates(&vk))
+        };
+
+        template_text = fill(
+            &template_text,
+            placeholder::GAMMA_ABC_CONSTANTS,
+            &gamma_abc_constants,
+        );
+
+        template_text = replace_vk_placeholders(
+            template_text.as_str(),
+            &vk,
+            &gamma_abc,
+            "\n        ",
+            options.pack_gamma_abc,
+        );
+
+        template_text = fill(
+            &template_text,
+            placeholder::VK_INPUT_LENGTH,
+            &input_count.to_string(),
+        );
+
+        let argument_location = match options.verify_tx_visibility {
+            Visibility::External if options.calldata_arguments => "calldata",
+            _ => "memory",
+        };
+
+        template_text = fill(
+            &template_text,
+            placeholder::ARGUMENT_LOCATION,
+            argument_location,
+        );
+
+        match &options.input_names {
+            Some(names) if !options.library => {
+                if names.len() != input_count {
+                    return Err(Error::InputCountMismatch {
+                        expected: input_count,
+                        found: names.len(),
+                    });
+                }
+
+                check_input_names(names)?;
+
+                let arguments: String = names
+                    .iter()
+                    .map(|name| format!(", bytes32 {}", name))
+                    .collect();
+                let assignments: String = names
+                    .iter()
+                    .enumerate()
+                    .map(|(i, name)| format!("\n        inputValues[{}] = uint256({});", i, name))
+                    .collect();
+
+                template_text = fill(&template_text, placeholder::INPUT_LOOP, &assignments);
+                template_text = fill(&template_text, placeholder::INPUT_ARGUMENT, &arguments);
+            }
+            _ => {
+                // feed input values only if there are any
+                let input_loop = if input_count > 0 {
+                    r#"
+        for(uint i = 0; i < input.length; i++){
+            inputValues[i] = input[i];
+        }"#
+                } else {
+                    ""
+                };
+                template_text = fill(&template_text, placeholder::INPUT_LOOP, input_loop);
+
+                // take input values as argument only if there are any
+                let input_argument = if input_count > 0 {
+                    format!(", uint[{}] {} input", input_count, argument_location)
+                } else {
+                    String::new()
+                };
+                template_text = fill(&template_text, placeholder::INPUT_ARGUMENT, &input_argument);
+            }
+        }
+
+        let solidity_pairing_lib =
+            with_precompile_addresses(&solidity_pairing_lib, &options.precompile_addresses);
+
+        let source = with_libraries(template_text, solidity_pairing_lib);
+
+        // the hash is a `bytes32` literal, which `with_libraries` would convert to `uint256`
+        let source = fill(
+            &source,
+            placeholder::VERIFYING_KEY_HASH,
+            &verifying_key_hash,
+        );
+
+        Ok(match &options.spdx_license_identifier {
+            Some(identifier) => format!("// SPDX-License-Identifier: {}\n{}", identifier, source),
+            None => source,
+        })
+    }
+
+    /// Exports the verifier as a file named after the contract, with a license identifier,
+    /// as expected by tools like Hardhat or Foundry. Fails like
+    /// `try_export_solidity_verifier_with_options`, in particular if `contract_name` is not an
+    /// identifier, which also keeps the file name free of path separators.
+    pub fn export_solidity_verifier_artifact(
+        vk: VerificationKey,
+        abi: SolidityAbi,
+        contract_name: &str,
+    ) -> Result<SolidityArtifact, Error> {
+        let source = G16::try_export_solidity_verifier_with_options(
+            vk,
+            abi,
+            &SolidityExportOptions {
+                contract_name: contract_name.to_string(),
+                spdx_license_identifier: Some(SPDX_LICENSE_IDENTIFIER.to_string()),
+                ..SolidityExportOptions::default()
+            },
+        )?;
+
+        Ok(SolidityArtifact {
+            filename: format!("{}.sol", contract_name),
+            source,
+        })
+    }
+
+    /// Checks that the default verifier exported for `vk` matches `expected`, for instance a
+    /// verifier committed to a repository. Whitespace is not significant: lines are trimmed,
+    /// runs of whitespace are collapsed and blank lines are ignored. On a mismatch, the error lists
+    /// the lines which differ.
+    pub fn assert_solidity_matches(
+        vk: VerificationKey,
+        abi: SolidityAbi,
+        expected: &str,
+    ) -> Result<(), Mismatch> {
+        let actual =
+            G16::export_solidity_verifier_with_options(vk, abi, &SolidityExportOptions::default());
+
+        match solidity_diff(expected, &actual) {
+            Some(diff) => Err(Mismatch { diff }),
+            None => Ok(()),
+        }
+    }
+
+    /// Exports a single verifier contract embedding several verification keys.
+    /// The key to verify against is selected by its position in `vks`, passed as the `id` argument of `verifyTx`.
+    /// The name attached to each key is only used to annotate the generated contract, in a comment in
+    /// which its control characters are escaped.
+    pub fn export_solidity_multi_verifier(
+        vks: &[(String, VerificationKey)],
+        abi: SolidityAbi,
+    ) -> String {
+        let (mut template_text, solidity_pairing_lib) = match abi {
+            SolidityAbi::V1 => (
+                String::from(MULTI_CONTRACT_TEMPLATE),
+                String::from(SOLIDITY_PAIRING_LIB),
+            ),
+            SolidityAbi::V2 => (
+                String::from(MULTI_CONTRACT_TEMPLATE_V2),
+                String::from(SOLIDITY_PAIRING_LIB_V2),
+            ),
+        };
+
+        let mut vk_branches_text = String::new();
+        for (id, (name, vk)) in vks.iter().enumerate() {
+            vk_branches_text.push_str(
+                format!(
+                    "if (id == {}) {{ // {}\n{}\n        }} else ",
+                    id,
+                    escape_control_characters(name),
+                    replace_vk_placeholders(
+                        MULTI_VK_BRANCH_TEMPLATE,
+                        vk,
+                        &gamma_abc_coordinates(vk),
+                        "\n            ",
+                        false
+                    )
+                )
+                .as_str(),
+            );
+        }
+
+        template_text = fill(&template_text, placeholder::VK_BRANCHES, &vk_branches_text);
+        template_text = fill(
+            &template_text,
+            placeholder::SCALAR_FIELD_MODULUS,
+            &scalar_field_modulus().to_string(),
+        );
+
+        with_libraries(template_text, solidity_pairing_lib)
+    }
+}
+
+/// Escapes the control characters and the Unicode line and paragraph separators of `text`, so
+/// that it cannot end a `//` comment
+fn escape_control_characters(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            c if c.is_control() || c == '\u{2028}' || c == '\u{2029}' => {
+                c.escape_default().to_string()
+            }
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+/// Splits `source` into lines with normalized whitespace, dropping blank lines
+fn normalized_lines(source: &str) -> Vec<String> {
+    source
+        .lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+/// A step of the edit script turning the lines of a source into the lines of another
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum LineEdit {
+    Keep,
+    /// the line at this index is only in the first source
+    Remove(usize),
+    /// the line at this index is only in the second source
+    Add(usize),
+}
+
+/// Returns the edit script turning `expected` into `actual` which keeps their longest common
+/// subsequence of lines
+fn line_edits(expected: &[String], actual: &[String]) -> Vec<LineEdit> {
+    // lengths of the longest common subsequences of the suffixes of both sources
+    let mut lcs = vec![vec![0usize; actual.len() + 1]; expected.len() + 1];
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            lcs[i][j] = if expected[i] == actual[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                std::cmp::max(lcs[i + 1][j], lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut edits = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < expected.len() || j < actual.len() {
+        if i < expected.len() && j < actual.len() && expected[i] == actual[j] {
+            edits.push(LineEdit::Keep);
+            i += 1;
+            j += 1;
+        } else if j == actual.len() || (i < expected.len() && lcs[i + 1][j] >= lcs[i][j + 1]) {
+            edits.push(LineEdit::Remove(i));
+            i += 1;
+        } else {
+            edits.push(LineEdit::Add(j));
+            j += 1;
+        }
+    }
+
+    edits
+}
+
+fn format_edit(edit: LineEdit, expected: &[String], actual: &[String]) -> Option<String> {
+    match edit {
+        LineEdit::Keep => None,
+        LineEdit::Remove(i) => Some(format!("-{:>5}: {}", i + 1, expected[i])),
+        LineEdit::Add(j) => Some(format!("+{:>5}: {}", j + 1, actual[j])),
+    }
+}
+
+/// A difference between an exported verifier and the expected one, as returned by
+/// `G16::assert_solidity_matches`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mismatch {
+    /// the lines which differ, prefixed with `-` if they are only expected and `+` if they are
+    /// only exported, with their line number among the non-blank lines
+    pub diff: String,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "the exported verifier does not match the expected one (- expected, + actual):\n{}",
+            self.diff
+        )
+    }
+}
+
+/// Returns the lines of the normalized sources which are only in `expected` or only in `actual`,
+/// prefixed with `-` and `+` respectively, or `None` if the sources match
+fn solidity_diff(expected: &str, actual: &str) -> Option<String> {
+    let expected = normalized_lines(expected);
+    let actual = normalized_lines(actual);
+
+    if expected == actual {
+        return None;
+    }
+
+    Some(
+        line_edits(&expected, &actual)
+            .into_iter()
+            .filter_map(|edit| format_edit(edit, &expected, &actual))
+            .collect::<Vec<_>>()
+            .join("\n"),
+    )
+}
+
+/// The changes between two verifiers, as returned by `diff_solidity_verifiers`. Each change is a
+/// line of the first verifier prefixed with `-` or of the second one prefixed with `+`, with its
+/// line number among the non-blank lines.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct VerifierDiff {
+    /// changed lines which only differ in hex literals, such as the points of the key
+    pub key_values: Vec<String>,
+    /// changed comments, pragmas and license identifiers, which do not change the code
+    pub boilerplate: Vec<String>,
+    /// all other changes, which change the code of the verifier
+    pub structure: Vec<String>,
+}
+
+impl VerifierDiff {
+    pub fn is_empty(&self) -> bool {
+        self.key_values.is_empty() && self.boilerplate.is_empty() && self.structure.is_empty()
+    }
+
+    /// Whether the verifiers run the same code against different keys
+    pub fn is_key_values_only(&self) -> bool {
+        !self.key_values.is_empty() && self.boilerplate.is_empty() && self.structure.is_empty()
+    }
+}
+
+/// Compares two verifiers line by line, ignoring whitespace like `G16::assert_solidity_matches`,
+/// and classifies the changed lines. A run of removed lines followed by as many added lines is a
+/// change of key values if each pair of lines only differs in hex literals. Changed comment, pragma
+/// and blank lines are boilerplate. All other changes are structural.
+pub fn diff_solidity_verifiers(a: &str, b: &str) -> VerifierDiff {
+    use lazy_static::lazy_static;
+
+    lazy_static! {
+        static ref HEX_LITERAL: Regex = Regex::new(r"0x[0-9a-fA-F]+").unwrap();
+    }
+
+    fn is_boilerplate(line: &str) -> bool {
+        line.starts_with("//") || line.starts_with("pragma ")
+    }
+
+    let a = normalized_lines(a);
+    let b = normalized_lines(b);
+
+    let mut diff = VerifierDiff::default();
+
+    let edits = line_edits(&a, &b);
+    for hunk in edits.split(|edit| *edit == LineEdit::Keep) {
+        let (boilerplate, code): (Vec<_>, Vec<_>) =
+            hunk.iter().cloned().partition(|edit| match edit {
+                LineEdit::Remove(i) => is_boilerplate(&a[*i]),
+                LineEdit::Add(j) => is_boilerplate(&b[*j]),
+                LineEdit::Keep => unreachable!(),
+            });
+
+        let removed: Vec<_> = code
+            .iter()
+            .filter_map(|edit| match edit {
+                LineEdit::Remove(i) => Some(&a[*i]),
+                _ => None,
+            })
+            .collect();
+        let added: Vec<_> = code
+            .iter()
+            .filter_map(|edit| match edit {
+                LineEdit::Add(j) => Some(&b[*j]),
+                _ => None,
+            })
+            .collect();
+
+        let key_values = removed.len() == added.len()
+            && removed
+                .iter()
+                .zip(&added)
+                .all(|(r, a)| HEX_LITERAL.replace_all(r, "0x") == HEX_LITERAL.replace_all(a, "0x"));
+
+        diff.boilerplate.extend(
+            boilerplate
+                .into_iter()
+                .filter_map(|edit| format_edit(edit, &a, &b)),
+        );
+
+        let changes = code
+            .into_iter()
+            .filter_map(|edit| format_edit(edit, &a, &b));
+        match key_values {
+            true => diff.key_values.extend(changes),
+            false => diff.structure.extend(changes),
+        }
+    }
+
+    diff
+}
+
+/// Checks that `arguments`, the JSON arguments a client passes to `verifyTx`, are laid out for a
+/// verifier exported with `abi`: the points of the proof as three arrays in V1 or as a single tuple
+/// in V2, as returned by `to_eth_v1` and `to_eth_v2`, followed by an array with the public inputs
+/// of `proof` if it has any. The values of the words are not checked.
+pub fn check_abi_compat(
+    proof: &Proof<ProofPoints>,
+    arguments: &[serde_json::Value],
+    abi: SolidityAbi,
+) -> Result<(), Error> {
+    use serde_json::Value;
+
+    fn words(value: &Value, count: usize) -> bool {
+        match value {
+            Value::Array(words) => words.len() == count && words.iter().all(Value::is_string),
+            _ => false,
+        }
+    }
+
+    fn g2(value: &Value) -> bool {
+        match value {
+            Value::Array(coordinates) => {
+                coordinates.len() == 2 && coordinates.iter().all(|c| words(c, 2))
+            }
+            _ => false,
+        }
+    }
+
+    fn points(a: &Value, b: &Value, c: &Value) -> bool {
+        words(a, 2) && g2(b) && words(c, 2)
+    }
+
+    let (found, inputs) = match arguments {
+        [a, b, c, inputs @ ..] if points(a, b, c) => (SolidityAbi::V1, inputs),
+        [Value::Array(proof), inputs @ ..] => match proof.as_slice() {
+            [a, b, c] if points(a, b, c) => (SolidityAbi::V2, inputs),
+            _ => {
+                return Err(Error::AbiMismatch {
+                    expected: abi,
+                    found: None,
+                })
+            }
+        },
+        _ => {
+            return Err(Error::AbiMismatch {
+                expected: abi,
+                found: None,
+            })
+        }
+    };
+
+    if found != abi {
+        return Err(Error::AbiMismatch {
+            expected: abi,
+            found: Some(found),
+        });
+    }
+
+    let input_count = match inputs {
+        [] => 0,
+        [Value::Array(inputs)] if inputs.iter().all(Value::is_string) => inputs.len(),
+        _ => {
+            return Err(Error::AbiMismatch {
+                expected: abi,
+                found: None,
+            })
+        }
+    };
+
+    if input_count != proof.inputs.len() {
+        return Err(Error::InputCountMismatch {
+            expected: proof.inputs.len(),
+            found: input_count,
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "cairo")]
+impl G16 {
+    /// Exports a Cairo verifier for Starknet, with the same structure as the Solidity verifier: the
+    /// inputs are checked to be in the scalar field, combined with the `gamma_abc` points into
+    /// `vk_x`, and the proof is accepted if the product of four pairings is one. Like the `require`
+    /// of the Solidity verifier, `verify` fails otherwise, so that callers cannot ignore its result.
+    ///
+    /// Starknet has no builtin for the alt_bn128 curve, so the contract imports the point types and
+    /// the curve operations from an `alt_bn128` module which must be provided at deployment. The
+    /// coordinates are split into the low and high 128 bits of a `Uint256`, in the order of the
+    /// Solidity export. Only keys on the bn128 curve are supported.
+    pub fn export_cairo_verifier(vk: VerificationKey) -> String {
+        fn g1_constants(name: &str, g1: &G1Affine) -> String {
+            vec![("X", &g1.0), ("Y", &g1.1)]
+                .into_iter()
+                .map(|(coordinate, value)| {
+                    let value = BigUint::parse_bytes(value.trim_start_matches("0x").as_bytes(), 16)
+                        .unwrap_or_else(|| panic!("Invalid coordinate of {}: {}", name, value));
+                    let value = format!("{:064x}", value);
+                    format!(
+                        "const {name}_{coordinate}_LOW = 0x{low};\nconst {name}_{coordinate}_HIGH = 0x{high};\n",
+                        name = name,
+                        coordinate = coordinate,
+                        low = &value[32..],
+                        high = &value[..32]
+                    )
+                })
+                .collect()
+        }
+
+        fn g2_constants(name: &str, g2: &G2Affine) -> String {
+            format!(
+                "{}{}",
+                g1_constants(&format!("{}_0", name), &g2.0),
+                g1_constants(&format!("{}_1", name), &g2.1)
+            )
+        }
+
+        fn uint256(name: &str) -> String {
+            format!("Uint256(low={0}_LOW, high={0}_HIGH)", name)
+        }
+
+        fn g1_point(name: &str) -> String {
+            format!(
+                "G1Point(x={}, y={})",
+                uint256(&format!("{}_X", name)),
+                uint256(&format!("{}_Y", name))
+            )
+        }
+
+        fn g2_point(name: &str) -> String {
+            format!(
+                "G2Point(x0={}, x1={}, y0={}, y1={})",
+                uint256(&format!("{}_0_X", name)),
+                uint256(&format!("{}_0_Y", name)),
+                uint256(&format!("{}_1_X", name)),
+                uint256(&format!("{}_1_Y", name))
+            )
+        }
+
+        let mut constants = g1_constants("VK_ALPHA", &vk.alpha);
+        constants.push_str(&g2_constants("VK_BETA", &vk.beta));
+        constants.push_str(&g2_constants("VK_GAMMA", &vk.gamma));
+        constants.push_str(&g2_constants("VK_DELTA", &vk.delta));
+        for (i, g1) in vk.gamma_abc.iter().enumerate() {
+            constants.push_str(&g1_constants(&format!("VK_GAMMA_ABC_{}", i), g1));
+        }
+
+        let vk_x_terms: String = (1..vk.gamma_abc.len())
+            .map(|i| {
+                format!(
+                    "    let (is_lt) = uint256_lt(inputs[{input}], snark_scalar_field);\n    \
+                     assert is_lt = 1;\n    \
+                     let (term) = g1_mul({point}, inputs[{input}]);\n    \
+                     let (vk_x) = g1_add(vk_x, term);\n",
+                    input = i - 1,
+                    point = g1_point(&format!("VK_GAMMA_ABC_{}", i))
+                )
+            })
+            .collect();
+
+        let template = fill(CAIRO_TEMPLATE, placeholder::VK_CONSTANTS, &constants);
+        let template = fill(
+            &template,
+            placeholder::VK_INPUT_LENGTH,
+            &vk.expected_public_input_count().to_string(),
+        );
+        let template = fill(
+            &template,
+            placeholder::VK_GAMMA_ABC_0,
+            &g1_point("VK_GAMMA_ABC_0"),
+        );
+        let template = fill(&template, placeholder::VK_X_TERMS, &vk_x_terms);
+
+        // a `Uint256` holds the low and high 128 bits
+        let modulus = scalar_field_modulus();
+        let template = fill(
+            &template,
+            placeholder::SCALAR_FIELD_MODULUS_LOW,
+            &format!("0x{:x}", &modulus & ((BigUint::from(1u32) << 128) - 1u32)),
+        );
+        let template = fill(
+            &template,
+            placeholder::SCALAR_FIELD_MODULUS_HIGH,
+            &format!("0x{:x}", modulus >> 128),
+        );
+        let template = fill(&template, placeholder::VK_ALPHA, &g1_point("VK_ALPHA"));
+        let template = fill(&template, placeholder::VK_BETA, &g2_point("VK_BETA"));
+        let template = fill(&template, placeholder::VK_GAMMA, &g2_point("VK_GAMMA"));
+        fill(&template, placeholder::VK_DELTA, &g2_point("VK_DELTA"))
+    }
+}
+
+/// The coordinates of the `gamma_abc` points of `vk`, as filled by `replace_vk_placeholders`
+fn gamma_abc_coordinates(vk: &VerificationKey) -> Vec<(String, String)> {
+    vk.gamma_abc
+        .iter()
+        .map(|g1| (g1.0.clone(), g1.1.clone()))
+        .collect()
+}
+
+/// Fills the verification key placeholders of `template` with the points of `vk`, except for the
+/// `gamma_abc` points, which are filled with the Solidity expressions of their coordinates in
+/// `gamma_abc`, such as the names of constants or the values returned by `gamma_abc_coordinates`.
+/// `separator` is inserted between the generated `gamma_abc` statements.
+/// If `packed` is set, the `gamma_abc` points are emitted as a single array literal which is unpacked in a loop.
+fn replace_vk_placeholders(
+    template: &str,
+    vk: &VerificationKey,
+    gamma_abc: &[(String, String)],
+    separator: &str,
+    packed: bool,
+) -> String {
+    let mut template_text = String::from(template);
+
+    template_text = fill(&template_text, placeholder::VK_ALPHA, &vk.alpha.to_string());
+    template_text = fill(&template_text, placeholder::VK_BETA, &vk.beta.to_string());
+    template_text = fill(&template_text, placeholder::VK_GAMMA, &vk.gamma.to_string());
+    template_text = fill(&template_text, placeholder::VK_DELTA, &vk.delta.to_string());
+
+    let gamma_abc_count: usize = gamma_abc.len();
+    template_text = fill(
+        &template_text,
+        placeholder::VK_GAMMA_ABC_LENGTH,
+        &gamma_abc_count.to_string(),
+    );
+
+    if packed {
+        let packed_text = format!(
+            "uint256[{}] memory gamma_abc_packed = [{}];{}for (uint i = 0; i < {}; i++) {{{}    vk.gamma_abc[i] = Pairing.G1Point(gamma_abc_packed[2 * i], gamma_abc_packed[2 * i + 1]);{}}}",
+            2 * gamma_abc_count,
+            gamma_abc
+                .iter()
+                .map(|(x, y)| format!("{}, {}", x, y))
+                .collect::<Vec<_>>()
+                .join(", "),
+            separator,
+            gamma_abc_count,
+            separator,
+            separator
+        );
+
+        return fill(&template_text, placeholder::VK_GAMMA_ABC_PTS, &packed_text);
+    }
+
+    let mut gamma_abc_repeat_text = String::new();
+    for (i, (x, y)) in gamma_abc.iter().enumerate() {
+        gamma_abc_repeat_text
+            .push_str(format!("vk.gamma_abc[{}] = Pairing.G1Point({}, {});", i, x, y).as_str());
+        if i + 1 < gamma_abc_count {
+            gamma_abc_repeat_text.push_str(separator);
+        }
+    }
+
+    fill(
+        &template_text,
+        placeholder::VK_GAMMA_ABC_PTS,
+        &gamma_abc_repeat_text,
+    )
+}
+
+/// Wraps the hex values of a filled contract template in `uint256` casts and prepends the libraries it depends on
+fn with_libraries(template_text: String, solidity_pairing_lib: String) -> String {
+    let re = Regex::new(r"(?P<v>0[xX][0-9a-fA-F]{64})").unwrap();
+    let template_text = re.replace_all(&template_text, "uint256($v)").to_string();
+
+    format!(
+        "{}{}{}",
+        SOLIDITY_G2_ADDITION_LIB, solidity_pairing_lib, template_text
+    )
+}
+
+const CONTRACT_TEMPLATE_V2: &str = r#"
+contract <%contract_name%> {
+    using Pairing for *;<%verified_event_declaration%><%gamma_abc_constants%>
+    struct VerifyingKey {
+        Pairing.G1Point alpha;
+        Pairing.G2Point beta;
+        Pairing.G2Point gamma;
+        Pairing.G2Point delta;
+        Pairing.G1Point[] gamma_abc;
+    }
+    struct Proof {
+        Pairing.G1Point a;
+        Pairing.G2Point b;
+        Pairing.G1Point c;
+    }
+    function verifyingKey() pure internal returns (VerifyingKey memory vk) {
+        vk.alpha = Pairing.G1Point(<%vk_alpha%>);
+        vk.beta = Pairing.G2Point(<%vk_beta%>);
+        vk.gamma = Pairing.G2Point(<%vk_gamma%>);
+        vk.delta = Pairing.G2Point(<%vk_delta%>);
+        vk.gamma_abc = new Pairing.G1Point[](<%vk_gamma_abc_length%>);
+        <%vk_gamma_abc_pts%>
+    }<%verifying_key_hash%>
+    function verify(uint[] memory input, Proof memory proof) <%verify_modifiers%> returns (uint) {
+        uint256 snark_scalar_field = <%scalar_field_modulus%>;
+        VerifyingKey memory vk = verifyingKey();
+        require(input.length + 1 == vk.gamma_abc.length<%input_count_message%>);
+        // Compute the linear combination vk_x
+        Pairing.G1Point memory vk_x = Pairing.G1Point(0, 0);
+        for (uint i = 0; i < input.length; i++) {
+            require(input[i] < snark_scalar_field<%input_range_message%>);
+            vk_x = Pairing.addition(vk_x, Pairing.scalar_mul(vk.gamma_abc[i + 1], input[i]));
+        }
+        vk_x = Pairing.addition(vk_x, vk.gamma_abc[0]);
+        if(!Pairing.pairingProd4(
+             proof.a, proof.b,
+             Pairing.negate(vk_x), vk.gamma,
+             Pairing.negate(proof.c), vk.delta,
+             Pairing.negate(vk.alpha), vk.beta)) return 1;
+        return 0;
+    }
+    function verifyTx(
+            Proof <%argument_location%> proof<%input_argument%>
+        ) <%verify_tx_modifiers%> returns (bool r) {
+        uint[] memory inputValues = new uint[](<%vk_input_length%>);
+        <%input_loop%>
+        if (verify(inputValues, proof) == 0) {<%verified_event_emit%>
+            return true;
+        } else {
+            return false;
+        }
+    }<%public_input_hash_wrapper%>
+}
+"#;
+
+const CONTRACT_TEMPLATE: &str = r#"
+contract <%contract_name%> {
+    using Pairing for *;<%verified_event_declaration%><%gamma_abc_constants%>
+    struct VerifyingKey {
+        Pairing.G1Point alpha;
+        Pairing.G2Point beta;
+        Pairing.G2Point gamma;
+        Pairing.G2Point delta;
+        Pairing.G1Point[] gamma_abc;
+    }
+    struct Proof {
+        Pairing.G1Point a;
+        Pairing.G2Point b;
+        Pairing.G1Point c;
+    }
+    function verifyingKey() pure internal returns (VerifyingKey memory vk) {
+        vk.alpha = Pairing.G1Point(<%vk_alpha%>);
+        vk.beta = Pairing.G2Point(<%vk_beta%>);
+        vk.gamma = Pairing.G2Point(<%vk_gamma%>);
+        vk.delta = Pairing.G2Point(<%vk_delta%>);
+        vk.gamma_abc = new Pairing.G1Point[](<%vk_gamma_abc_length%>);
+        <%vk_gamma_abc_pts%>
+    }<%verifying_key_hash%>
+    function verify(uint[] memory input, Proof memory proof) <%verify_modifiers%> returns (uint) {
+        uint256 snark_scalar_field = <%scalar_field_modulus%>;
+        VerifyingKey memory vk = verifyingKey();
+        require(input.length + 1 == vk.gamma_abc.length<%input_count_message%>);
+        // Compute the linear combination vk_x
+        Pairing.G1Point memory vk_x = Pairing.G1Point(0, 0);
+        for (uint i = 0; i < input.length; i++) {
+            require(input[i] < snark_scalar_field<%input_range_message%>);
+            vk_x = Pairing.addition(vk_x, Pairing.scalar_mul(vk.gamma_abc[i + 1], input[i]));
+        }
+        vk_x = Pairing.addition(vk_x, vk.gamma_abc[0]);
+        if(!Pairing.pairingProd4(
+             proof.a, proof.b,
+             Pairing.negate(vk_x), vk.gamma,
+             Pairing.negate(proof.c), vk.delta,
+             Pairing.negate(vk.alpha), vk.beta)) return 1;
+        return 0;
+    }
+    function verifyTx(
+            uint[2] <%argument_location%> a,
+            uint[2][2] <%argument_location%> b,
+            uint[2] <%argument_location%> c<%input_argument%>
+        ) <%verify_tx_modifiers%> returns (bool r) {
+        Proof memory proof;
+        proof.a = Pairing.G1Point(a[0], a[1]);
+        proof.b = Pairing.G2Point([b[0][0], b[0][1]], [b[1][0], b[1][1]]);
+        proof.c = Pairing.G1Point(c[0], c[1]);
+        uint[] memory inputValues = new uint[](<%vk_input_length%>);
+        <%input_loop%>
+        if (verify(inputValues, proof) == 0) {<%verified_event_emit%>
+            return true;
+        } else {
+            return false;
+        }
+    }<%public_input_hash_wrapper%>
+}
+"#;
+
+const MULTI_VK_BRANCH_TEMPLATE: &str = r#"            vk.alpha = Pairing.G1Point(<%vk_alpha%>);
+            vk.beta = Pairing.G2Point(<%vk_beta%>);
+            vk.gamma = Pairing.G2Point(<%vk_gamma%>);
+            vk.delta = Pairing.G2Point(<%vk_delta%>);
+            vk.gamma_abc = new Pairing.G1Point[](<%vk_gamma_abc_length%>);
+            <%vk_gamma_abc_pts%>"#;
+
+const MULTI_CONTRACT_TEMPLATE_V2: &str = r#"
+contract Verifier {
+    using Pairing for *;
+    struct VerifyingKey {
+        Pairing.G1Point alpha;
+        Pairing.G2Point beta;
+        Pairing.G2Point gamma;
+        Pairing.G2Point delta;
+        Pairing.G1Point[] gamma_abc;
+    }
+    struct Proof {
+        Pairing.G1Point a;
+        Pairing.G2Point b;
+        Pairing.G1Point c;
+    }
+    function verifyingKey(uint id) pure internal returns (VerifyingKey memory vk) {
+        <%vk_branches%>{
+            revert("Unknown verifying key");
+        }
+    }
+    function verify(uint id, uint[] memory input, Proof memory proof) internal view returns (uint) {
+        uint256 snark_scalar_field = <%scalar_field_modulus%>;
+        VerifyingKey memory vk = verifyingKey(id);
+        require(input.length + 1 == vk.gamma_abc.length);
+        // Compute the linear combination vk_x
+        Pairing.G1Point memory vk_x = Pairing.G1Point(0, 0);
+        for (uint i = 0; i < input.length; i++) {
+            require(input[i] < snark_scalar_field);
+            vk_x = Pairing.addition(vk_x, Pairing.scalar_mul(vk.gamma_abc[i + 1], input[i]));
+        }
+        vk_x = Pairing.addition(vk_x, vk.gamma_abc[0]);
+        if(!Pairing.pairingProd4(
+             proof.a, proof.b,
+             Pairing.negate(vk_x), vk.gamma,
+             Pairing.negate(proof.c), vk.delta,
+             Pairing.negate(vk.alpha), vk.beta)) return 1;
+        return 0;
+    }
+    function verifyTx(
+            uint id,
+            Proof memory proof,
+            uint[] memory input
+        ) public view returns (bool r) {
+        if (verify(id, input, proof) == 0) {
+            return true;
+        } else {
+            return false;
+        }
+    }
+}
+"#;
+
+const MULTI_CONTRACT_TEMPLATE: &str = r#"
+contract Verifier {
+    using Pairing for *;
+    struct VerifyingKey {
+        Pairing.G1Point alpha;
+        Pairing.G2Point beta;
+        Pairing.G2Point gamma;
+        Pairing.G2Point delta;
+        Pairing.G1Point[] gamma_abc;
+    }
+    struct Proof {
+        Pairing.G1Point a;
+        Pairing.G2Point b;
+        Pairing.G1Point c;
+    }
+    function verifyingKey(uint id) pure internal returns (VerifyingKey memory vk) {
+        <%vk_branches%>{
+            revert("Unknown verifying key");
+        }
+    }
+    function verify(uint id, uint[] memory input, Proof memory proof) internal view returns (uint) {
+        uint256 snark_scalar_field = <%scalar_field_modulus%>;
+        VerifyingKey memory vk = verifyingKey(id);
+        require(input.length + 1 == vk.gamma_abc.length);
+        // Compute the linear combination vk_x
+        Pairing.G1Point memory vk_x = Pairing.G1Point(0, 0);
+        for (uint i = 0; i < input.length; i++) {
+            require(input[i] < snark_scalar_field);
+            vk_x = Pairing.addition(vk_x, Pairing.scalar_mul(vk.gamma_abc[i + 1], input[i]));
+        }
+        vk_x = Pairing.addition(vk_x, vk.gamma_abc[0]);
+        if(!Pairing.pairingProd4(
+             proof.a, proof.b,
+             Pairing.negate(vk_x), vk.gamma,
+             Pairing.negate(proof.c), vk.delta,
+             Pairing.negate(vk.alpha), vk.beta)) return 1;
+        return 0;
+    }
+    function verifyTx(
+            uint id,
+            uint[2] memory a,
+            uint[2][2] memory b,
+            uint[2] memory c,
+            uint[] memory input
+        ) public view returns (bool r) {
+        Proof memory proof;
+        proof.a = Pairing.G1Point(a[0], a[1]);
+        proof.b = Pairing.G2Point([b[0][0], b[0][1]], [b[1][0], b[1][1]]);
+        proof.c = Pairing.G1Point(c[0], c[1]);
+        if (verify(id, input, proof) == 0) {
+            return true;
+        } else {
+            return false;
+        }
+    }
+}
+"#;
+
+#[cfg(feature = "cairo")]
+const CAIRO_TEMPLATE: &str = r#"%lang starknet
+
+from starkware.cairo.common.uint256 import Uint256, uint256_lt
+from alt_bn128 import G1Point, G2Point, g1_add, g1_mul, g1_negate, pairing_prod_4
+
+<%vk_constants%>
+const SNARK_SCALAR_FIELD_LOW = <%scalar_field_modulus_low%>;
+const SNARK_SCALAR_FIELD_HIGH = <%scalar_field_modulus_high%>;
+
+// Compute the linear combination vk_x
+func compute_vk_x{range_check_ptr}(inputs: Uint256*) -> (vk_x: G1Point) {
+    let snark_scalar_field = Uint256(low=SNARK_SCALAR_FIELD_LOW, high=SNARK_SCALAR_FIELD_HIGH);
+    let vk_x = <%vk_gamma_abc_0%>;
+<%vk_x_terms%>    return (vk_x=vk_x);
+}
+
+@view
+func verify{range_check_ptr}(
+    a: G1Point, b: G2Point, c: G1Point, inputs_len: felt, inputs: Uint256*
+) -> (res: felt) {
+    assert inputs_len = <%vk_input_length%>;
+    let (vk_x) = compute_vk_x(inputs);
+    let (neg_vk_x) = g1_negate(vk_x);
+    let (neg_c) = g1_negate(c);
+    let (neg_alpha) = g1_negate(<%vk_alpha%>);
+    let (res) = pairing_prod_4(
+        a, b,
+        neg_vk_x, <%vk_gamma%>,
+        neg_c, <%vk_delta%>,
+        neg_alpha, <%vk_beta%>);
+    assert res = 1;
+    return (res=res);
+}
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::flat_absy::FlatVariable;
+    use crate::ir::{Function, Prog, QuadComb, Statement};
+    use crate::proof_system::bellman::groth16::setup_and_prove;
+    use crate::proof_system::identity_program;
+    use crate::proof_system::{G1Affine, G2Affine, ProofSystem};
+    use zokrates_field::{Bn128Field, Field};
+
+    #[test]
+    fn export_without_public_inputs() {
+        let program: Prog<Bn128Field> = Prog {
+            main: Function {
+                id: String::from("main"),
+                arguments: vec![FlatVariable::new(0)],
+                returns: vec![],
+                statements: vec![Statement::Constraint(
+                    QuadComb::from_linear_combinations(
+                        FlatVariable::new(0).into(),
+                        FlatVariable::new(0).into(),
+                    ),
+                    FlatVariable::new(1).into(),
+                )],
+            },
+            private: vec![true],
+        };
+
+        let keypair = G16::setup(program);
+
+        for abi in vec![SolidityAbi::V1, SolidityAbi::V2] {
+            let verifier =
+                <G16 as ProofSystem<Bn128Field>>::export_solidity_verifier(keypair.vk.clone(), abi);
+
+            let start = verifier.find("function verifyTx(").unwrap();
+            let end = start + verifier[start..].find("\n    }\n").unwrap();
+            let verify_tx = &verifier[start..end];
+
+            let parameters = &verify_tx[..verify_tx.find(") ").unwrap()];
+            assert!(!parameters.contains("input"));
+            assert!(!verify_tx.contains("input."));
+            assert!(!verify_tx.contains("input["));
+            assert!(verify_tx.contains("uint[] memory inputValues = new uint[](0);"));
+        }
+    }
+
+    #[test]
+    fn export_with_visibility_and_mutability() {
+        let keypair = G16::setup(identity_program());
+
+        let default = <G16 as ProofSystem<Bn128Field>>::export_solidity_verifier(
+            keypair.vk.clone(),
+            SolidityAbi::V1,
+        );
+        assert!(default.contains("Proof memory proof) internal view returns (uint) {"));
+        assert!(default.contains(") public view returns (bool r) {"));
+
+        let verifier = G16::export_solidity_verifier_with_options(
+            keypair.vk,
+            SolidityAbi::V1,
+            &SolidityExportOptions {
+                verify_tx_visibility: Visibility::External,
+                verify_mutability: Mutability::NonPayable,
+                ..SolidityExportOptions::default()
+            },
+        );
+        assert!(verifier.contains("Proof memory proof) internal returns (uint) {"));
+        assert!(verifier.contains(") external view returns (bool r) {"));
+    }
+
+    #[test]
+    fn curve_params_in_templates() {
+        // the verifiers rely on the precompiles for BN128, whose scalar field is filled in the
+        // templates and whose base field is written in the pairing libraries
+        let params = Bn128Field::curve_params();
+        let vk = G16::setup(identity_program()).vk;
+
+        let scalar_field = format!("uint256 snark_scalar_field = {};", params.scalar_modulus);
+        for abi in vec![SolidityAbi::V1, SolidityAbi::V2] {
+            let verifier =
+                <G16 as ProofSystem<Bn128Field>>::export_solidity_verifier(vk.clone(), abi);
+            assert!(verifier.contains(&scalar_field));
+
+            let multi_verifier =
+                G16::export_solidity_multi_verifier(&[(String::from("identity"), vk.clone())], abi);
+            assert!(multi_verifier.contains(&scalar_field));
+        }
+
+        let base_field = format!("uint q = {};", params.base_modulus);
+        for library in &[SOLIDITY_PAIRING_LIB, SOLIDITY_PAIRING_LIB_V2] {
+            assert!(library.contains(&base_field));
+        }
+    }
+
+    #[cfg(feature = "cairo")]
+    #[test]
+    fn curve_params_in_cairo_template() {
+        let cairo = G16::export_cairo_verifier(G16::setup(identity_program()).vk);
+
+        assert!(
+            cairo.contains("const SNARK_SCALAR_FIELD_LOW = 0x2833e84879b9709143e1f593f0000001;")
+        );
+        assert!(
+            cairo.contains("const SNARK_SCALAR_FIELD_HIGH = 0x30644e72e131a029b85045b68181585d;")
+        );
+    }
+
+    #[test]
+    fn precompile_calls_in_assembly() {
+        // the pairing libraries call the precompiles for G1 addition (6), G1 scalar
+        // multiplication (7) and pairing (8) from inline assembly
+        for library in &[SOLIDITY_PAIRING_LIB, SOLIDITY_PAIRING_LIB_V2] {
+            let lines = library.lines().map(|l| l.trim()).collect::<Vec<_>>();
+
+            for precompile in 6..=8 {
+                let call = format!("(sub(gas(), 2000), {},", precompile);
+                let position = lines.iter().position(|l| l.contains(&call)).unwrap();
+
+                assert_eq!(lines[position - 1], "assembly {");
+            }
+        }
+    }
+
+    #[cfg(feature = "cairo")]
+    #[test]
+    fn export_cairo_verifier() {
+        let vk = <G16 as ProofSystem<Bn128Field>>::setup(identity_program()).vk;
+        let cairo = G16::export_cairo_verifier(vk.clone());
+
+        assert!(cairo.starts_with("%lang starknet"));
+        assert!(cairo.contains("@view\nfunc verify{range_check_ptr}("));
+        assert!(cairo.contains("assert inputs_len = 2;"));
+        assert!(cairo.contains("assert res = 1;\n    return (res=res);"));
+        assert!(!cairo.contains("<%"));
+
+        for (i, g1) in vk.gamma_abc.iter().enumerate() {
+            let x = g1.0.trim_start_matches("0x");
+            assert!(cairo.contains(&format!("const VK_GAMMA_ABC_{}_X_LOW = 0x{};", i, &x[32..])));
+            assert!(cairo.contains(&format!(
+                "const VK_GAMMA_ABC_{}_X_HIGH = 0x{};",
+                i,
+                &x[..32]
+            )));
+            assert!(cairo.contains(&format!("VK_GAMMA_ABC_{}_Y_LOW", i)));
+        }
+        assert!(!cairo.contains("VK_GAMMA_ABC_3"));
+        assert!(cairo.contains("let (term) = g1_mul(G1Point(x=Uint256(low=VK_GAMMA_ABC_2_X_LOW"));
+    }
+
+    #[test]
+    fn check_abi_compat() {
+        let (_, proof) = setup_and_prove(42);
+
+        let inputs = serde_json::to_value(&proof.inputs).unwrap();
+        let mut v1 = proof.proof.to_eth_v1();
+        v1.push(inputs.clone());
+        let mut v2 = proof.proof.to_eth_v2();
+        v2.push(inputs);
+
+        assert_eq!(
+            super::check_abi_compat(&proof, &v1, SolidityAbi::V1),
+            Ok(())
+        );
+        assert_eq!(
+            super::check_abi_compat(&proof, &v2, SolidityAbi::V2),
+            Ok(())
+        );
+
+        assert_eq!(
+            super::check_abi_compat(&proof, &v1, SolidityAbi::V2),
+            Err(Error::AbiMismatch {
+                expected: SolidityAbi::V2,
+                found: Some(SolidityAbi::V1)
+            })
+        );
+        assert_eq!(
+            super::check_abi_compat(&proof, &v2, SolidityAbi::V1),
+            Err(Error::AbiMismatch {
+                expected: SolidityAbi::V1,
+                found: Some(SolidityAbi::V2)
+            })
+        );
+
+        // the public inputs are missing
+        assert_eq!(
+            super::check_abi_compat(&proof, &v2[..1], SolidityAbi::V2),
+            Err(Error::InputCountMismatch {
+                expected: 2,
+                found: 0
+            })
+        );
+
+        // `b` is flattened
+        let mut flat = v1.clone();
+        flat[1] = serde_json::to_value(vec![&(proof.proof.b.0).0, &(proof.proof.b.0).1]).unwrap();
+        assert_eq!(
+            super::check_abi_compat(&proof, &flat, SolidityAbi::V1),
+            Err(Error::AbiMismatch {
+                expected: SolidityAbi::V1,
+                found: None
+            })
+        );
+    }
+
+    #[test]
+    fn export_named_gamma_abc() {
+        let keypair = G16::setup(identity_program());
+
+        let default = <G16 as ProofSystem<Bn128Field>>::export_solidity_verifier(
+            keypair.vk.clone(),
+            SolidityAbi::V2,
+        );
+        assert!(!default.contains("constant VK_GAMMA_ABC"));
+
+        for abi in vec![SolidityAbi::V1, SolidityAbi::V2] {
+            let verifier = G16::export_solidity_verifier_with_options(
+                keypair.vk.clone(),
+                abi,
+                &SolidityExportOptions {
+                    name_gamma_abc: true,
+                    ..SolidityExportOptions::default()
+                },
+            );
+
+            for (i, g1) in keypair.vk.gamma_abc.iter().enumerate() {
+                assert!(verifier.contains(&format!(
+                    "    uint256 constant VK_GAMMA_ABC_{}_X = uint256({});\n    uint256 constant VK_GAMMA_ABC_{}_Y = uint256({});\n",
+                    i, g1.0, i, g1.1
+                )));
+                assert!(verifier.contains(&format!(
+                    "vk.gamma_abc[{}] = Pairing.G1Point(VK_GAMMA_ABC_{}_X, VK_GAMMA_ABC_{}_Y);",
+                    i, i, i
+                )));
+            }
+        }
+
+        // the packed array literal refers to the constants as well
+        let packed = G16::export_solidity_verifier_with_options(
+            keypair.vk,
+            SolidityAbi::V2,
+            &SolidityExportOptions {
+                name_gamma_abc: true,
+                pack_gamma_abc: true,
+                ..SolidityExportOptions::default()
+            },
+        );
+        assert!(packed.contains(
+            "uint256[6] memory gamma_abc_packed = [VK_GAMMA_ABC_0_X, VK_GAMMA_ABC_0_Y, VK_GAMMA_ABC_1_X"
+        ));
+    }
+
+    #[test]
+    fn export_calldata_arguments() {
+        let keypair = G16::setup(identity_program());
+
+        let export = |abi: SolidityAbi, visibility: Visibility, calldata_arguments: bool| {
+            let verifier = G16::export_solidity_verifier_with_options(
+                keypair.vk.clone(),
+                abi,
+                &SolidityExportOptions {
+                    verify_tx_visibility: visibility,
+                    calldata_arguments,
+                    ..SolidityExportOptions::default()
+                },
+            );
+            let start = verifier.find("function verifyTx(").unwrap();
+            let end = start + verifier[start..].find(')').unwrap();
+            verifier[start..end].to_string()
+        };
+
+        assert!(export(SolidityAbi::V2, Visibility::External, true)
+            .contains("Proof calldata proof, uint[2] calldata input"));
+        assert!(export(SolidityAbi::V1, Visibility::External, true).contains(
+            "uint[2] calldata a,\n            uint[2][2] calldata b,\n            uint[2] calldata c, uint[2] calldata input"
+        ));
+
+        for abi in vec![SolidityAbi::V1, SolidityAbi::V2] {
+            // memory by default, and for functions which are not external
+            for signature in vec![
+                export(abi, Visibility::External, false),
+                export(abi, Visibility::Public, false),
+                export(abi, Visibility::Public, true),
+            ] {
+                assert!(signature.contains("uint[2] memory input"));
+                assert!(!signature.contains("calldata"));
+            }
+        }
+    }
+
+    #[test]
+    fn export_require_messages() {
+        let keypair = G16::setup(identity_program());
+
+        let default = <G16 as ProofSystem<Bn128Field>>::export_solidity_verifier(
+            keypair.vk.clone(),
+            SolidityAbi::V2,
+        );
+        assert!(!default.contains("ZoKrates:"));
+        assert!(default.contains("require(input.length + 1 == vk.gamma_abc.length);"));
+
+        for abi in vec![SolidityAbi::V1, SolidityAbi::V2] {
+            for library in vec![false, true] {
+                let verifier = G16::export_solidity_verifier_with_options(
+                    keypair.vk.clone(),
+                    abi,
+                    &SolidityExportOptions {
+                        require_messages: true,
+                        library,
+                        ..SolidityExportOptions::default()
+                    },
+                );
+
+                let start = verifier.find("function verify(").unwrap();
+                let verify = &verifier[start..start + verifier[start..].find("\n    }").unwrap()];
+
+                assert!(verify.contains(
+                    r#"require(input.length + 1 == vk.gamma_abc.length, "ZoKrates: wrong number of public inputs");"#
+                ));
+                assert!(verify.contains(
+                    r#"require(input[i] < snark_scalar_field, "ZoKrates: input out of field range");"#
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn export_vk_hash() {
+        let keypair = G16::setup(identity_program());
+
+        let default = <G16 as ProofSystem<Bn128Field>>::export_solidity_verifier(
+            keypair.vk.clone(),
+            SolidityAbi::V2,
+        );
+        assert!(!default.contains("vkHash"));
+
+        let commitment = hex::encode(keypair.vk.keccak256_commitment());
+
+        for abi in vec![SolidityAbi::V1, SolidityAbi::V2] {
+            let verifier = G16::export_solidity_verifier_with_options(
+                keypair.vk.clone(),
+                abi,
+                &SolidityExportOptions {
+                    vk_hash: true,
+                    ..SolidityExportOptions::default()
+                },
+            );
+
+            let function = "function vkHash() public pure returns (bytes32) {\n        return 0x";
+            let start = verifier.find(function).unwrap() + function.len();
+            assert_eq!(&verifier[start..start + 64], commitment);
+            assert_eq!(&verifier[start + 64..start + 65], ";");
+        }
+    }
+
+    #[test]
+    fn export_input_names() {
+        // the argument is private, so that the program has two public outputs
+        let program: Prog<Bn128Field> = Prog {
+            main: Function {
+                id: String::from("main"),
+                arguments: vec![FlatVariable::new(0)],
+                returns: vec![FlatVariable::public(0), FlatVariable::public(1)],
+                statements: vec![
+                    Statement::Constraint(
+                        FlatVariable::new(0).into(),
+                        FlatVariable::public(0).into(),
+                    ),
+                    Statement::Constraint(
+                        FlatVariable::new(0).into(),
+                        FlatVariable::public(1).into(),
+                    ),
+                ],
+            },
+            private: vec![true],
+        };
+
+        let keypair = G16::setup(program);
+
+        let options = SolidityExportOptions {
+            input_names: Some(vec![String::from("root"), String::from("leaf")]),
+            ..SolidityExportOptions::default()
+        };
+
+        let v2 = G16::export_solidity_verifier_with_options(
+            keypair.vk.clone(),
+            SolidityAbi::V2,
+            &options,
+        );
+        assert!(v2.contains("Proof memory proof, bytes32 root, bytes32 leaf\n"));
+        assert!(v2.contains(
+            "inputValues[0] = uint256(root);\n        inputValues[1] = uint256(leaf);\n"
+        ));
+        assert!(!v2.contains("memory input\n"));
+
+        let v1 = G16::export_solidity_verifier_with_options(
+            keypair.vk.clone(),
+            SolidityAbi::V1,
+            &options,
+        );
+        assert!(v1.contains("uint[2] memory c, bytes32 root, bytes32 leaf\n"));
+
+        assert_eq!(
+            G16::try_export_solidity_verifier_with_options(
+                keypair.vk.clone(),
+                SolidityAbi::V2,
+                &SolidityExportOptions {
+                    input_names: Some(vec![String::from("root")]),
+                    ..SolidityExportOptions::default()
+                },
+            ),
+            Err(Error::InputCountMismatch {
+                expected: 2,
+                found: 1
+            })
+        );
+
+        let export = |names: &[&str]| {
+            G16::try_export_solidity_verifier_with_options(
+                keypair.vk.clone(),
+                SolidityAbi::V2,
+                &SolidityExportOptions {
+                    input_names: Some(names.iter().map(|n| n.to_string()).collect()),
+                    ..SolidityExportOptions::default()
+                },
+            )
+        };
+        let invalid = |name: &str, reason: &str| {
+            Err(Error::InvalidInputName {
+                name: name.to_string(),
+                reason: reason.to_string(),
+            })
+        };
+
+        assert!(export(&["$root", "leaf_1"]).is_ok());
+        assert_eq!(
+            export(&["root", "x) public {} function drain("]),
+            invalid(
+                "x) public {} function drain(",
+                "expected a Solidity identifier"
+            )
+        );
+        assert_eq!(
+            export(&["1root", "leaf"]),
+            invalid("1root", "expected a Solidity identifier")
+        );
+        assert_eq!(export(&["root", "root"]), invalid("root", "used twice"));
+        for reserved in RESERVED_INPUT_NAMES {
+            assert_eq!(
+                export(&["root", reserved]),
+                invalid(reserved, "already used by `verifyTx`")
+            );
+        }
+    }
+
+    #[test]
+    fn export_library() {
+        let keypair = G16::setup(identity_program());
+
+        let options = SolidityExportOptions {
+            library: true,
+            contract_name: String::from("SquareVerifier"),
+            verify_visibility: Visibility::Public,
+            emit_verified_event: true,
+            ..SolidityExportOptions::default()
+        };
+
+        for abi in vec![SolidityAbi::V1, SolidityAbi::V2] {
+            let verifier =
+                G16::export_solidity_verifier_with_options(keypair.vk.clone(), abi, &options);
+
+            assert!(verifier.contains("\nlibrary SquareVerifier {\n"));
+            assert!(!verifier.contains("contract "));
+            assert!(verifier.contains(
+                "function verify(uint[] memory input, Proof memory proof) internal view returns (uint) {"
+            ));
+            assert!(verifier.contains("function verifyingKey() pure internal"));
+            assert!(!verifier.contains("verifyTx"));
+            assert!(!verifier.contains("ProofVerified"));
+            assert!(verifier.ends_with("        return 0;\n    }\n}\n"));
+        }
+    }
+
+    #[test]
+    fn export_public_input_hash() {
+        // the input is private, so that the output is the only public input
+        let program = Prog {
+            private: vec![true],
+            ..identity_program()
+        };
+
+        let keypair = G16::setup(program);
+
+        let default = <G16 as ProofSystem<Bn128Field>>::export_solidity_verifier(
+            keypair.vk.clone(),
+            SolidityAbi::V2,
+        );
+        assert!(!default.contains("verifyTxWithPreimage"));
+
+        let scalar_field = format!(
+            "uint256 snark_scalar_field = {};",
+            Bn128Field::curve_params().scalar_modulus
+        );
+        for (hash, function) in vec![
+            (PublicInputHash::Keccak256, "keccak256"),
+            (PublicInputHash::Sha256, "sha256"),
+        ] {
+            let options = SolidityExportOptions {
+                public_input_hash: Some(hash),
+                ..SolidityExportOptions::default()
+            };
+
+            let v1 = G16::export_solidity_verifier_with_options(
+                keypair.vk.clone(),
+                SolidityAbi::V1,
+                &options,
+            );
+            let v2 = G16::export_solidity_verifier_with_options(
+                keypair.vk.clone(),
+                SolidityAbi::V2,
+                &options,
+            );
+
+            for verifier in &[&v1, &v2] {
+                assert!(verifier
+                    .contains("bytes memory preimage\n        ) public view returns (bool r) {"));
+                // in `verifyTx` and in the wrapper
+                assert_eq!(verifier.matches(&scalar_field).count(), 2);
+                assert!(verifier.contains(&format!(
+                    "input[0] = uint256({}(preimage)) % snark_scalar_field;",
+                    function
+                )));
+            }
+            assert!(v1.contains("return verifyTx(a, b, c, input);"));
+            assert!(v2.contains("return verifyTx(proof, input);"));
+        }
+
+        // a `pure` wrapper cannot call an external `verifyTx` through `this`
+        let external = |mutability: Mutability| {
+            G16::export_solidity_verifier_with_options(
+                keypair.vk.clone(),
+                SolidityAbi::V2,
+                &SolidityExportOptions {
+                    public_input_hash: Some(PublicInputHash::Keccak256),
+                    verify_tx_visibility: Visibility::External,
+                    verify_tx_mutability: mutability,
+                    ..SolidityExportOptions::default()
+                },
+            )
+        };
+        for (mutability, modifiers) in vec![
+            (Mutability::Pure, "external view"),
+            (Mutability::View, "external view"),
+            (Mutability::NonPayable, "external"),
+        ] {
+            let verifier = external(mutability);
+            assert!(verifier.contains(&format!(
+                "bytes memory preimage\n        ) {} returns (bool r) {{",
+                modifiers
+            )));
+            assert!(verifier.contains("return this.verifyTx(proof, input);"));
+        }
+    }
+
+    #[test]
+    fn export_public_input_hash_with_several_inputs() {
+        let keypair = G16::setup(identity_program());
+
+        assert_eq!(
+            G16::try_export_solidity_verifier_with_options(
+                keypair.vk,
+                SolidityAbi::V2,
+                &SolidityExportOptions {
+                    public_input_hash: Some(PublicInputHash::Keccak256),
+                    ..SolidityExportOptions::default()
+                },
+            ),
+            Err(Error::InputCountMismatch {
+                expected: 1,
+                found: 2
+            })
+        );
+    }
+
+    #[test]
+    fn export_with_precompile_addresses() {
+        let keypair = G16::setup(identity_program());
+
+        let options = SolidityExportOptions {
+            precompile_addresses: PrecompileAddresses {
+                ec_add: 0x106,
+                ec_mul: 0x107,
+                ec_pairing: 0x108,
+            },
+            ..SolidityExportOptions::default()
+        };
+
+        for (abi, call) in vec![
+            (SolidityAbi::V1, "staticcall(sub(gas(), 2000), "),
+            (SolidityAbi::V2, "call(sub(gas(), 2000), "),
+        ] {
+            let verifier =
+                G16::export_solidity_verifier_with_options(keypair.vk.clone(), abi, &options);
+
+            for address in &[262, 263, 264] {
+                assert_eq!(
+                    verifier.matches(&format!("{}{},", call, address)).count(),
+                    1
+                );
+            }
+            for address in &[6, 7, 8] {
+                assert!(!verifier.contains(&format!("{}{},", call, address)));
+            }
+            // the modular exponentiation precompile of the G2 addition library is not affected
+            assert!(verifier.contains("staticcall(sub(gas(), 2000), 5, "));
+        }
+    }
+
+    #[test]
+    fn export_without_gamma_abc() {
+        let mut vk = G16::setup(identity_program()).vk;
+        vk.gamma_abc.clear();
+
+        for abi in vec![SolidityAbi::V1, SolidityAbi::V2] {
+            assert_eq!(
+                G16::try_export_solidity_verifier_with_options(
+                    vk.clone(),
+                    abi,
+                    &SolidityExportOptions::default()
+                ),
+                Err(Error::MissingPoint(String::from("gamma_abc[0]")))
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot export verifier: Missing point `gamma_abc[0]`")]
+    fn export_solidity_verifier_without_gamma_abc() {
+        let mut vk = G16::setup(identity_program()).vk;
+        vk.gamma_abc.clear();
+
+        <G16 as ProofSystem<Bn128Field>>::export_solidity_verifier(vk, SolidityAbi::V1);
+    }
+
+    #[test]
+    fn export_verified_event() {
+        let keypair = G16::setup(identity_program());
+
+        let default = <G16 as ProofSystem<Bn128Field>>::export_solidity_verifier(
+            keypair.vk.clone(),
+            SolidityAbi::V2,
+        );
+        assert!(!default.contains("ProofVerified"));
+
+        for abi in vec![SolidityAbi::V1, SolidityAbi::V2] {
+            let verifier = G16::export_solidity_verifier_with_options(
+                keypair.vk.clone(),
+                abi,
+                &SolidityExportOptions {
+                    emit_verified_event: true,
+                    ..SolidityExportOptions::default()
+                },
+            );
+
+            assert!(verifier
+                .contains("    using Pairing for *;\n    event ProofVerified(uint[] input);\n"));
+            assert!(verifier.contains(
+                "if (verify(inputValues, proof) == 0) {\n            emit ProofVerified(inputValues);\n            return true;"
+            ));
+            assert!(verifier.contains(") public returns (bool r) {"));
+            assert!(!verifier.contains(") public view returns (bool r) {"));
+        }
+
+        // a payable verifyTx stays payable
+        let payable = G16::export_solidity_verifier_with_options(
+            keypair.vk,
+            SolidityAbi::V2,
+            &SolidityExportOptions {
+                emit_verified_event: true,
+                verify_tx_mutability: Mutability::Payable,
+                ..SolidityExportOptions::default()
+            },
+        );
+        assert!(payable.contains(") public payable returns (bool r) {"));
+    }
+
+    #[test]
+    fn export_is_deterministic() {
+        let keypair = G16::setup(identity_program());
+
+        // a key read back from its serialization yields the same verifier
+        let reparsed: VerificationKey =
+            serde_json::from_str(&serde_json::to_string(&keypair.vk).unwrap()).unwrap();
+
+        for abi in &["v1", "v2"] {
+            let export = |vk: &VerificationKey| {
+                <G16 as ProofSystem<Bn128Field>>::export_solidity_verifier(
+                    vk.clone(),
+                    SolidityAbi::from(abi).unwrap(),
+                )
+            };
+
+            let verifier = export(&keypair.vk);
+            assert_eq!(verifier, export(&keypair.vk));
+            assert_eq!(verifier, export(&reparsed));
+
+            // whitespace is not significant
+            let reformatted = verifier
+                .lines()
+                .map(|line| format!("\t{}  ", line.replace(" ", "   ")))
+                .collect::<Vec<_>>()
+                .join("\n\n");
+            G16::assert_solidity_matches(
+                keypair.vk.clone(),
+                SolidityAbi::from(abi).unwrap(),
+                &reformatted,
+            )
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn solidity_diff() {
+        let expected = "contract Verifier {\n    uint a;\n    uint b;\n}\n";
+
+        assert_eq!(
+            super::solidity_diff(expected, "contract  Verifier {\n\nuint a;\nuint b;\n}"),
+            None
+        );
+        assert_eq!(
+            super::solidity_diff(
+                expected,
+                "contract Verifier {\n    uint a;\n    uint c;\n    uint d;\n}\n"
+            ),
+            Some(String::from(
+                "-    3: uint b;\n+    3: uint c;\n+    4: uint d;"
+            ))
+        );
+    }
+
+    #[test]
+    fn diff_solidity_verifiers() {
+        let keypair = G16::setup(identity_program());
+
+        let export = |vk: &VerificationKey, options: &SolidityExportOptions| {
+            G16::export_solidity_verifier_with_options(vk.clone(), SolidityAbi::V2, options)
+        };
+        let default = SolidityExportOptions::default();
+
+        let verifier = export(&keypair.vk, &default);
+        assert!(super::diff_solidity_verifiers(&verifier, &verifier).is_empty());
+
+        // another valid key which only differs in a `gamma_abc` point
+        let mut other_vk = keypair.vk.clone();
+        other_vk.gamma_abc[1] = keypair.vk.gamma_abc[0].clone();
+        let diff = super::diff_solidity_verifiers(&verifier, &export(&other_vk, &default));
+        assert!(diff.is_key_values_only());
+        assert_eq!(diff.key_values.len(), 2);
+        assert!(diff.key_values[0].starts_with('-'));
+        assert!(diff.key_values[0].contains("vk.gamma_abc[1] = Pairing.G1Point("));
+        assert!(diff.key_values[1].starts_with('+'));
+
+        // a license comment is boilerplate
+        let diff = super::diff_solidity_verifiers(
+            &verifier,
+            &export(
+                &keypair.vk,
+                &SolidityExportOptions {
+                    spdx_license_identifier: Some(String::from("MIT")),
+                    ..SolidityExportOptions::default()
+                },
+            ),
+        );
+        assert!(!diff.boilerplate.is_empty());
+        assert!(diff.key_values.is_empty());
+        assert!(diff.structure.is_empty());
+
+        // revert reasons change the code
+        let diff = super::diff_solidity_verifiers(
+            &export(&other_vk, &default),
+            &export(
+                &keypair.vk,
+                &SolidityExportOptions {
+                    require_messages: true,
+                    ..SolidityExportOptions::default()
+                },
+            ),
+        );
+        assert!(!diff.is_key_values_only());
+        assert_eq!(diff.key_values.len(), 2);
+        assert_eq!(diff.structure.len(), 4);
+    }
+
+    #[test]
+    fn assert_solidity_matches_mismatch() {
+        let keypair = G16::setup(identity_program());
+
+        let verifier = <G16 as ProofSystem<Bn128Field>>::export_solidity_verifier(
+            keypair.vk.clone(),
+            SolidityAbi::V1,
+        )
+        .replace(
+            "require(input.length + 1 == vk.gamma_abc.length);",
+            "require(input.length == vk.gamma_abc.length);",
+        );
+
+        let mismatch =
+            G16::assert_solidity_matches(keypair.vk, SolidityAbi::V1, &verifier).unwrap_err();
+
+        let lines: Vec<_> = mismatch.diff.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with('-'));
+        assert!(lines[0].ends_with(": require(input.length == vk.gamma_abc.length);"));
+        assert!(lines[1].starts_with('+'));
+        assert!(lines[1].ends_with(": require(input.length + 1 == vk.gamma_abc.length);"));
+    }
+
+    #[test]
+    fn export_artifact() {
+        let keypair = G16::setup(identity_program());
+
+        let artifact = G16::export_solidity_verifier_artifact(
+            keypair.vk.clone(),
+            SolidityAbi::V2,
+            "MyVerifier",
+        )
+        .unwrap();
+
+        assert_eq!(artifact.filename, "MyVerifier.sol");
+        assert!(artifact
+            .source
+            .starts_with("// SPDX-License-Identifier: LGPL-3.0-only AND MIT\n"));
+        assert_eq!(
+            artifact.source.matches("SPDX-License-Identifier").count(),
+            1
+        );
+        assert!(artifact.source.contains("pragma solidity ^0.6.1;"));
+        assert_eq!(artifact.source.matches("\ncontract ").count(), 1);
+        assert!(artifact.source.contains("\ncontract MyVerifier {"));
+
+        assert_eq!(
+            G16::export_solidity_verifier_artifact(keypair.vk, SolidityAbi::V2, "../Verifier"),
+            Err(Error::InvalidContractName(String::from("../Verifier")))
+        );
+    }
+
+    #[test]
+    fn export_invalid_contract_name() {
+        let vk = G16::setup(identity_program()).vk;
+
+        let export = |contract_name: &str| {
+            G16::try_export_solidity_verifier_with_options(
+                vk.clone(),
+                SolidityAbi::V2,
+                &SolidityExportOptions {
+                    contract_name: contract_name.to_string(),
+                    ..SolidityExportOptions::default()
+                },
+            )
+        };
+
+        for valid in &["Verifier", "_Verifier", "$verifier2", "V_1$"] {
+            assert!(export(valid).is_ok());
+        }
+        for invalid in &[
+            "",
+            "1Verifier",
+            "My Verifier",
+            "Verifier {}\ncontract X",
+            "Vérifier",
+        ] {
+            assert_eq!(
+                export(invalid),
+                Err(Error::InvalidContractName(invalid.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn export_invalid_license_identifier() {
+        let vk = G16::setup(identity_program()).vk;
+
+        let export = |identifier: &str| {
+            G16::try_export_solidity_verifier_with_options(
+                vk.clone(),
+                SolidityAbi::V2,
+                &SolidityExportOptions {
+                    spdx_license_identifier: Some(identifier.to_string()),
+                    ..SolidityExportOptions::default()
+                },
+            )
+        };
+
+        for valid in &[
+            "MIT",
+            "LGPL-3.0-only AND MIT",
+            "(MIT OR Apache-2.0)",
+            "GPL-2.0+ WITH Classpath-exception-2.0",
+            "LicenseRef-23",
+            "DocumentRef-spdx-tool-1.2:LicenseRef-MIT-Style-2",
+        ] {
+            assert!(export(valid)
+                .unwrap()
+                .starts_with(&format!("// SPDX-License-Identifier: {}\n", valid)));
+        }
+        for invalid in &[
+            "",
+            "MIT\ncontract Evil {}",
+            "MIT\r",
+            "MIT */",
+            "MIT // comment",
+            "MIT\u{2028}",
+        ] {
+            assert_eq!(
+                export(invalid),
+                Err(Error::InvalidLicenseIdentifier(invalid.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn export_packed_gamma_abc() {
+        let g1 = |i: u8| G1Affine::new(format!("0x{:064x}", i), format!("0x{:064x}", i + 100));
+        let g2 = G2Affine::new(g1(0), g1(0));
+
+        let vk = VerificationKey {
+            alpha: g1(0),
+            beta: g2.clone(),
+            gamma: g2.clone(),
+            delta: g2,
+            gamma_abc: (1..5).map(g1).collect(),
+            producer_version: None,
+        };
+
+        let verifier = G16::export_solidity_verifier_with_options(
+            vk,
+            SolidityAbi::V2,
+            &SolidityExportOptions {
+                pack_gamma_abc: true,
+                ..SolidityExportOptions::default()
+            },
+        );
+
+        assert!(!verifier.contains("vk.gamma_abc[0] = Pairing.G1Point("));
+        assert!(verifier.contains("vk.gamma_abc = new Pairing.G1Point[](4);"));
+        assert!(verifier.contains(&format!(
+            "uint256[8] memory gamma_abc_packed = [{}];",
+            (1..5)
+                .flat_map(|i| vec![i, i + 100])
+                .map(|i| format!("uint256(0x{:064x})", i))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )));
+        assert!(verifier.contains("for (uint i = 0; i < 4; i++) {"));
+        assert!(verifier.contains(
+            "vk.gamma_abc[i] = Pairing.G1Point(gamma_abc_packed[2 * i], gamma_abc_packed[2 * i + 1]);"
+        ));
+    }
+
+    #[test]
+    fn validate_template() {
+        use crate::proof_system::solidity::{template_placeholders, validate_template};
+
+        assert_eq!(validate_template(CONTRACT_TEMPLATE), Ok(()));
+        assert_eq!(validate_template(CONTRACT_TEMPLATE_V2), Ok(()));
+        assert!(template_placeholders().contains(&"vk_gamma_abc_pts"));
+        assert!(template_placeholders().contains(&"input_loop"));
+
+        let custom = CONTRACT_TEMPLATE_V2.replace("<%verifying_key_hash%>", "<%foo%>");
+        assert_eq!(
+            validate_template(&custom),
+            Err(String::from("Unknown placeholder `<%foo%>`"))
+        );
+
+        let custom = CONTRACT_TEMPLATE_V2.replace("<%vk_gamma_abc_pts%>", "");
+        assert_eq!(
+            validate_template(&custom),
+            Err(String::from("Missing placeholder `<%vk_gamma_abc_pts%>`"))
+        );
+
+        // the keys of a multi-verifier are filled in its branches
+        assert_eq!(validate_template(MULTI_CONTRACT_TEMPLATE), Ok(()));
+        assert_eq!(validate_template(MULTI_CONTRACT_TEMPLATE_V2), Ok(()));
+        assert_eq!(validate_template(MULTI_VK_BRANCH_TEMPLATE), Ok(()));
+        assert!(template_placeholders().contains(&"vk_branches"));
+
+        let custom = MULTI_CONTRACT_TEMPLATE_V2.replace("<%vk_branches%>", "");
+        assert_eq!(
+            validate_template(&custom),
+            Err(String::from("Missing placeholder `<%vk_alpha%>`"))
+        );
+
+        // the points are filled by name, in any order
+        let custom = CONTRACT_TEMPLATE_V2
+            .replace("<%vk_beta%>", "<%tmp%>")
+            .replace("<%vk_gamma%>", "<%vk_beta%>")
+            .replace("<%tmp%>", "<%vk_gamma%>");
+        assert_eq!(validate_template(&custom), Ok(()));
+    }
+
+    #[test]
+    fn export_multi_verifier() {
+        let program = |private| -> Prog<Bn128Field> {
+            Prog {
+                main: Function {
+                    id: String::from("main"),
+                    arguments: vec![FlatVariable::new(0)],
+                    returns: vec![FlatVariable::public(0)],
+                    statements: vec![Statement::Constraint(
+                        FlatVariable::new(0).into(),
+                        FlatVariable::public(0).into(),
+                    )],
+                },
+                private: vec![private],
+            }
+        };
+
+        let first = <G16 as ProofSystem<Bn128Field>>::setup(program(false));
+        let second = <G16 as ProofSystem<Bn128Field>>::setup(program(true));
+
+        let verifier = G16::export_solidity_multi_verifier(
+            &[
+                (String::from("public_identity"), first.vk),
+                (String::from("private_identity"), second.vk),
+            ],
+            SolidityAbi::V2,
+        );
+
+        assert!(verifier.contains("function verifyingKey(uint id)"));
+        assert!(verifier.contains("if (id == 0) { // public_identity"));
+        assert!(verifier.contains("} else if (id == 1) { // private_identity"));
+        assert!(verifier.contains("revert(\"Unknown verifying key\");"));
+        assert!(verifier.contains("uint id,"));
+    }
+
+    #[test]
+    fn export_solidity_multi_verifier_escapes_names() {
+        let vk = G16::setup(identity_program()).vk;
+
+        let verifier = G16::export_solidity_multi_verifier(
+            &[(
+                String::from("identity\nfunction drain() public {}\r\u{2028}"),
+                vk,
+            )],
+            SolidityAbi::V2,
+        );
+
+        assert!(verifier
+            .contains("if (id == 0) { // identity\\nfunction drain() public {}\\r\\u{2028}\n"));
+        assert!(!verifier.contains("\nfunction drain()"));
+    }
+
+    #[test]
+    fn to_eth_v1_v2() {
+        fn words(value: &serde_json::Value) -> Vec<String> {
+            match value {
+                serde_json::Value::String(word) => vec![word.clone()],
+                serde_json::Value::Array(values) => values.iter().flat_map(words).collect(),
+                _ => panic!("unexpected value {}", value),
+            }
+        }
+
+        let proof = setup_and_prove(42).1.proof;
+
+        let v1 = proof.to_eth_v1();
+        let v2 = proof.to_eth_v2();
+
+        // `a`, `b` and `c` in V1, a single `Proof` in V2
+        assert_eq!(v1.len(), 3);
+        assert_eq!(v2.len(), 1);
+        assert_eq!(v2[0], serde_json::Value::Array(v1.clone()));
+
+        // V1 builds the `Proof` of V2 with `G2Point([b[0][0], b[0][1]], [b[1][0], b[1][1]])`
+        assert!(CONTRACT_TEMPLATE
+            .contains("proof.b = Pairing.G2Point([b[0][0], b[0][1]], [b[1][0], b[1][1]]);"));
+        let expected = vec![
+            &proof.a.0,
+            &proof.a.1,
+            &(proof.b.0).0,
+            &(proof.b.0).1,
+            &(proof.b.1).0,
+            &(proof.b.1).1,
+            &proof.c.0,
+            &proof.c.1,
+        ];
+        let v1_words = v1.iter().flat_map(words).collect::<Vec<_>>();
+        assert_eq!(v1_words.iter().collect::<Vec<_>>(), expected);
+        assert_eq!(v1_words, v2.iter().flat_map(words).collect::<Vec<_>>());
+    }
+}
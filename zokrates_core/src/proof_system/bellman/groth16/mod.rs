@@ -0,0 +1,2915 @@
+use bellman::groth16::{Parameters, Proof as BellmanProof, VerifyingKey};
+use pairing::ff::PrimeField;
+use pairing::{CurveAffine, CurveProjective, EncodedPoint, Engine};
+use sha3::{Digest, Keccak256};
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::sync::Arc;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+
+use zokrates_field::{visit_field, Bls12Field, Bn128Field, Field, FieldVisitor};
+
+use crate::ir;
+use crate::proof_system::bellman::rand::Rng;
+use crate::proof_system::bellman::Computation;
+use crate::proof_system::bellman::{parse_fr, parse_g1, parse_g2};
+use num_bigint::BigUint;
+use proof_system::{
+    check_producer_version, parse_field_input, G1Affine, G2Affine, Proof, ProofSystem,
+    SetupKeypair, SolidityAbi, ZOKRATES_VERSION,
+};
+
+mod export;
+mod verification;
+
+pub use self::export::*;
+pub use self::verification::*;
+
+/// The name of the scheme, as used by the CLI
+const G16_SCHEME: &str = "g16";
+
+// gas prices of the alt_bn128 precompiles, see EIP-1108
+const EC_ADD_GAS: u64 = 150;
+const EC_MUL_GAS: u64 = 6_000;
+const PAIRING_BASE_GAS: u64 = 45_000;
+const PAIRING_PER_PAIR_GAS: u64 = 34_000;
+
+const G16_WARNING: &str = "WARNING: You are using the G16 scheme which is subject to malleability. See zokrates.github.io/toolbox/proving_schemes.html#g16-malleability for implications.";
+
+pub struct G16 {}
+
+/// The acknowledgment that G16 proofs are malleable, returned by `G16::acknowledge_malleability`.
+/// The setups and proofs which take it do not print `G16_WARNING`.
+#[derive(Debug, Clone, Copy)]
+pub struct MalleabilityAcknowledged(());
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ProofPoints {
+    pub a: G1Affine,
+    pub b: G2Affine,
+    pub c: G1Affine,
+}
+
+impl ProofPoints {
+    fn is_canonical<T: Field>(&self) -> bool {
+        serialization::is_canonical_g1::<T>(&self.a)
+            && serialization::is_canonical_g2::<T>(&self.b)
+            && serialization::is_canonical_g1::<T>(&self.c)
+    }
+
+    /// Decodes the points, rejecting points which are not on the curve of `T` and the point at infinity
+    pub fn into_bellman<T: Field>(self) -> Result<BellmanProof<T::BellmanEngine>, Error> {
+        self.to_bellman::<T>()
+    }
+
+    pub fn to_bellman<T: Field>(&self) -> Result<BellmanProof<T::BellmanEngine>, Error> {
+        self.to_bellman_with_order::<T>(G2Order::default())
+    }
+
+    /// Decodes the points like `to_bellman`, reading the components of `b` in the given `order`
+    pub fn to_bellman_with_order<T: Field>(
+        &self,
+        order: G2Order,
+    ) -> Result<BellmanProof<T::BellmanEngine>, Error> {
+        Ok(BellmanProof {
+            a: decode_finite_g1::<T>("a", &self.a)?,
+            b: decode_finite_g2::<T>("b", &self.b, order)?,
+            c: decode_finite_g1::<T>("c", &self.c)?,
+        })
+    }
+
+    /// Returns the proof arguments of `verifyTx` in a verifier exported with `SolidityAbi::V1`,
+    /// where `a`, `b` and `c` are passed as separate arrays
+    pub fn to_eth_v1(&self) -> Vec<serde_json::Value> {
+        let (a, b, c) = self.eth_points();
+        vec![
+            serde_json::to_value(a).unwrap(),
+            serde_json::to_value(b).unwrap(),
+            serde_json::to_value(c).unwrap(),
+        ]
+    }
+
+    /// Returns the proof argument of `verifyTx` in a verifier exported with `SolidityAbi::V2`,
+    /// where the proof is passed as a single `Proof` struct, encoded as the tuple of its points.
+    /// The words are the same as the ones of `to_eth_v1`, only grouped differently.
+    pub fn to_eth_v2(&self) -> Vec<serde_json::Value> {
+        vec![serde_json::to_value(self.eth_points()).unwrap()]
+    }
+
+    /// The coordinates of `a`, `b` and `c` in the layout of the verifier's `G1Point` and `G2Point`
+    fn eth_points(&self) -> ([&String; 2], [[&String; 2]; 2], [&String; 2]) {
+        (
+            [&self.a.0, &self.a.1],
+            [
+                [&(self.b.0).0, &(self.b.0).1],
+                [&(self.b.1).0, &(self.b.1).1],
+            ],
+            [&self.c.0, &self.c.1],
+        )
+    }
+
+    pub fn from_bellman<T: Field>(proof: &BellmanProof<T::BellmanEngine>) -> Self {
+        ProofPoints {
+            a: parse_g1::<T>(&proof.a),
+            b: parse_g2::<T>(&proof.b),
+            c: parse_g1::<T>(&proof.c),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct VerificationKey {
+    alpha: G1Affine,
+    beta: G2Affine,
+    gamma: G2Affine,
+    delta: G2Affine,
+    gamma_abc: Vec<G1Affine>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    producer_version: Option<String>,
+}
+
+/// Keys are equal if their points are, whichever version of ZoKrates generated them
+impl PartialEq for VerificationKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.alpha == other.alpha
+            && self.beta == other.beta
+            && self.gamma == other.gamma
+            && self.delta == other.delta
+            && self.gamma_abc == other.gamma_abc
+    }
+}
+
+impl VerificationKey {
+    /// The version of ZoKrates which ran the setup of the key, see `check_producer_version`. Keys
+    /// which were not generated by a setup, or before the version was recorded, have none.
+    pub fn producer_version(&self) -> Option<&str> {
+        self.producer_version.as_deref()
+    }
+
+    fn is_canonical<T: Field>(&self) -> bool {
+        serialization::is_canonical_g1::<T>(&self.alpha)
+            && serialization::is_canonical_g2::<T>(&self.beta)
+            && serialization::is_canonical_g2::<T>(&self.gamma)
+            && serialization::is_canonical_g2::<T>(&self.delta)
+            && self
+                .gamma_abc
+                .iter()
+                .all(|g1| serialization::is_canonical_g1::<T>(g1))
+    }
+
+    /// The coordinates of the key as 32 byte big endian words, in the order in which they
+    /// appear in the exported verifier: `alpha`, `beta`, `gamma`, `delta`, then `gamma_abc`
+    ///
+    /// # Panics
+    ///
+    /// Panics if a coordinate is not a hex encoded value of at most 32 bytes
+    pub fn to_eth_words(&self) -> Vec<[u8; 32]> {
+        fn g1_words(g1: &G1Affine) -> Vec<[u8; 32]> {
+            vec![to_eth_word(&g1.0), to_eth_word(&g1.1)]
+        }
+
+        fn g2_words(g2: &G2Affine) -> Vec<[u8; 32]> {
+            let mut words = g1_words(&g2.0);
+            words.extend(g1_words(&g2.1));
+            words
+        }
+
+        let mut words = g1_words(&self.alpha);
+        words.extend(g2_words(&self.beta));
+        words.extend(g2_words(&self.gamma));
+        words.extend(g2_words(&self.delta));
+        for g1 in &self.gamma_abc {
+            words.extend(g1_words(g1));
+        }
+        words
+    }
+
+    /// Writes the key as JSON, in the format of its `Serialize` implementation. The JSON is
+    /// serialized directly into the writer instead of being built as a string first, so that keys
+    /// with many `gamma_abc` points can be written without holding a second copy in memory.
+    /// Small writes are frequent, so `w` should be buffered.
+    pub fn write_json<W: Write>(&self, w: W) -> io::Result<()> {
+        serde_json::to_writer(w, self).map_err(io::Error::from)
+    }
+
+    /// The number of public inputs of a proof for this key, one less than the number of `gamma_abc`
+    /// points. A malformed key without `gamma_abc` points expects none.
+    pub fn expected_public_input_count(&self) -> usize {
+        self.gamma_abc.len().saturating_sub(1)
+    }
+
+    /// Describes the checks of the exported verifier for this key in plain text: the number of
+    /// public inputs, their range, and the pairing equation with `vk_x` expanded over the
+    /// `gamma_abc` points. The verifier checks the equation in the equivalent form
+    /// `e(A, B) * e(-vk_x, gamma) * e(-C, delta) * e(-alpha, beta) = 1`.
+    pub fn describe_equation(&self) -> String {
+        let count = self.expected_public_input_count();
+
+        let vk_x = match self.gamma_abc.len() {
+            0 => String::from("0"),
+            len => std::iter::once(String::from("gamma_abc[0]"))
+                .chain((1..len).map(|i| format!("input[{}] * gamma_abc[{}]", i - 1, i)))
+                .collect::<Vec<_>>()
+                .join(" + "),
+        };
+
+        format!(
+            "e(A, B) = e(alpha, beta) * e(vk_x, gamma) * e(C, delta)\n\
+             where vk_x = {}\n\
+             for {} public input{} input[i], each smaller than the scalar field modulus r",
+            vk_x,
+            count,
+            if count == 1 { "" } else { "s" }
+        )
+    }
+
+    /// The point `gamma_abc[0] + inputs[0] * gamma_abc[1] + ...` which the exported verifier
+    /// computes as `vk_x` before the pairing check. Comparing it with the value computed on-chain
+    /// tells whether the contract received the intended public inputs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of inputs is not `expected_public_input_count`, or if a `gamma_abc`
+    /// point is not on the curve of `T`
+    pub fn compute_vk_x<T: Field>(&self, inputs: &[T]) -> G1Affine {
+        assert_eq!(
+            inputs.len(),
+            self.expected_public_input_count(),
+            "Expected {} public inputs",
+            self.expected_public_input_count()
+        );
+
+        let gamma_abc: Vec<_> = self
+            .gamma_abc
+            .iter()
+            .enumerate()
+            .map(|(i, g1)| {
+                decode_g1::<T>(&format!("gamma_abc[{}]", i), g1).unwrap_or_else(|e| panic!("{}", e))
+            })
+            .collect();
+
+        let mut vk_x = gamma_abc[0].into_projective();
+        for (input, g1) in inputs.iter().zip(&gamma_abc[1..]) {
+            vk_x.add_assign(&g1.mul(input.clone().into_bellman().into_repr()));
+        }
+
+        let vk_x = vk_x.into_affine();
+        if vk_x.is_zero() {
+            let zero = format!("0x{:064x}", 0);
+            G1Affine(zero.clone(), zero)
+        } else {
+            parse_g1::<T>(&vk_x)
+        }
+    }
+
+    /// Returns whether proofs for both keys take the same number of public inputs. Keys from
+    /// different setups of the same program always have the same interface, but the converse does
+    /// not hold: programs with the same number of public inputs can have different constraints.
+    pub fn same_interface(&self, other: &VerificationKey) -> bool {
+        self.gamma_abc.len() == other.gamma_abc.len()
+    }
+
+    /// The number of `Pairing.scalar_mul` calls the exported verifier performs, one per public input
+    pub fn onchain_scalar_mul_count(&self) -> usize {
+        self.expected_public_input_count()
+    }
+
+    /// Reads a key from words in the layout of `to_eth_words`.
+    /// The points are not checked to be on a curve.
+    pub fn from_eth_words(words: &[[u8; 32]]) -> Result<Self, Error> {
+        // alpha, beta, gamma, delta and at least one gamma_abc point
+        if words.len() < 16 || words.len() % 2 != 0 {
+            return Err(Error::InvalidWordCount(words.len()));
+        }
+
+        let coordinate = |i: usize| format!("0x{}", hex::encode(&words[i]));
+        let g1 = |i: usize| G1Affine::new(coordinate(i), coordinate(i + 1));
+        let g2 = |i: usize| G2Affine::new(g1(i), g1(i + 2));
+
+        Ok(VerificationKey {
+            alpha: g1(0),
+            beta: g2(2),
+            gamma: g2(6),
+            delta: g2(10),
+            gamma_abc: (14..words.len()).step_by(2).map(g1).collect(),
+            producer_version: None,
+        })
+    }
+
+    /// A rough estimate of the gas the exported verifier spends in the alt_bn128 precompiles
+    /// (EIP-1108 prices), i.e. one scalar multiplication and one addition per public input, a final
+    /// addition and a pairing check over 4 pairs. Calldata and execution outside the precompiles
+    /// are not included.
+    pub fn onchain_gas_estimate(&self) -> u64 {
+        let scalar_muls = self.onchain_scalar_mul_count() as u64;
+
+        scalar_muls * Self::marginal_input_gas()
+            + EC_ADD_GAS
+            + PAIRING_BASE_GAS
+            + 4 * PAIRING_PER_PAIR_GAS
+    }
+
+    /// The gas the exported verifier spends in the alt_bn128 precompiles for each public input,
+    /// i.e. one scalar multiplication and one addition (EIP-1108 prices). This is how much
+    /// `onchain_gas_estimate` grows when a circuit gets one more public input.
+    pub fn marginal_input_gas() -> u64 {
+        EC_MUL_GAS + EC_ADD_GAS
+    }
+
+    /// The keccak256 hash of the concatenated `to_eth_words`, which is what
+    /// `keccak256(abi.encodePacked(...))` returns for the same `uint256` words in Solidity
+    pub fn keccak256_commitment(&self) -> [u8; 32] {
+        let mut hasher = Keccak256::new();
+        for word in self.to_eth_words() {
+            hasher.input(&word[..]);
+        }
+
+        let mut commitment = [0u8; 32];
+        commitment.copy_from_slice(hasher.result().as_slice());
+        commitment
+    }
+
+    fn from_bellman<T: Field>(vk: &VerifyingKey<T::BellmanEngine>) -> Self {
+        VerificationKey {
+            alpha: parse_g1::<T>(&vk.alpha_g1),
+            beta: parse_g2::<T>(&vk.beta_g2),
+            gamma: parse_g2::<T>(&vk.gamma_g2),
+            delta: parse_g2::<T>(&vk.delta_g2),
+            gamma_abc: vk.ic.iter().map(|g1| parse_g1::<T>(g1)).collect(),
+            producer_version: None,
+        }
+    }
+
+    /// Converts the key generated by a setup of this version of ZoKrates, which it is tagged with
+    fn from_setup<T: Field>(vk: &VerifyingKey<T::BellmanEngine>) -> Self {
+        VerificationKey {
+            producer_version: Some(ZOKRATES_VERSION.to_string()),
+            ..VerificationKey::from_bellman::<T>(vk)
+        }
+    }
+
+    /// Reads a key written by bellman's `VerifyingKey::write`, e.g. by users of bellman who do not
+    /// use the JSON encoding of ZoKrates. Trailing bytes are rejected.
+    pub fn from_bellman_bytes<T: Field>(bytes: &[u8]) -> Result<Self, Error> {
+        let mut reader = bytes;
+        let vk = VerifyingKey::<T::BellmanEngine>::read(&mut reader)
+            .map_err(|e| Error::InvalidBellmanKey(e.to_string()))?;
+        if !reader.is_empty() {
+            return Err(Error::InvalidBellmanKey(format!(
+                "{} trailing bytes",
+                reader.len()
+            )));
+        }
+
+        Ok(VerificationKey::from_bellman::<T>(&vk))
+    }
+
+    /// Decodes the points into a bellman key, rejecting points which are not on the curve of `T`.
+    /// Only the `gamma_abc` points may be the point at infinity.
+    ///
+    /// The key does not hold `beta` and `delta` in G1, which bellman only uses to create proofs, so
+    /// `beta_g1` and `delta_g1` are set to the generator of G1. The result can be prepared and used
+    /// to verify proofs, but not to create them.
+    pub fn to_bellman<T: Field>(&self) -> Result<VerifyingKey<T::BellmanEngine>, Error> {
+        self.to_bellman_with_order::<T>(G2Order::default())
+    }
+
+    /// Decodes the points like `to_bellman`, reading the components of `beta`, `gamma` and `delta`
+    /// in the given `order`
+    pub fn to_bellman_with_order<T: Field>(
+        &self,
+        order: G2Order,
+    ) -> Result<VerifyingKey<T::BellmanEngine>, Error> {
+        Ok(VerifyingKey {
+            alpha_g1: decode_finite_g1::<T>("alpha", &self.alpha)?,
+            beta_g1: <T::BellmanEngine as Engine>::G1Affine::one(), // not used during verification
+            beta_g2: decode_finite_g2::<T>("beta", &self.beta, order)?,
+            gamma_g2: decode_finite_g2::<T>("gamma", &self.gamma, order)?,
+            delta_g1: <T::BellmanEngine as Engine>::G1Affine::one(), // not used during verification
+            delta_g2: decode_finite_g2::<T>("delta", &self.delta, order)?,
+            ic: self
+                .gamma_abc
+                .iter()
+                .enumerate()
+                .map(|(i, g1)| decode_g1::<T>(&format!("gamma_abc[{}]", i), g1))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    /// Checks the points of the key without verifying a proof: each of them must be on the curve of
+    /// `T` and in its prime order subgroup, and only the `gamma_abc` points may be the point at
+    /// infinity. The error names the first invalid point.
+    pub fn validate<T: Field>(&self) -> Result<(), Error> {
+        in_subgroup("alpha", decode_finite_g1::<T>("alpha", &self.alpha)?)?;
+        for (name, g2) in &[
+            ("beta", &self.beta),
+            ("gamma", &self.gamma),
+            ("delta", &self.delta),
+        ] {
+            in_subgroup(name, decode_finite_g2::<T>(name, g2, G2Order::default())?)?;
+        }
+        for (i, g1) in self.gamma_abc.iter().enumerate() {
+            let name = format!("gamma_abc[{}]", i);
+            in_subgroup(&name, decode_g1::<T>(&name, g1)?)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Error {
+    /// A point required to build a key or proof was not provided
+    MissingPoint(String),
+    /// A point could not be decoded to a point on the curve
+    InvalidPoint { point: String, reason: String },
+    /// A public input could not be decoded to a field element
+    InvalidPublicInput { value: String, reason: String },
+    /// The number of public inputs does not match the verification key
+    InputCountMismatch { expected: usize, found: usize },
+    /// A proving key could not be read
+    InvalidProvingKey(String),
+    /// A list of words does not have the length of an encoded verification key
+    InvalidWordCount(usize),
+    /// A prepared verification key could not be read
+    InvalidPreparedKey(String),
+    /// A bundle was created for another proving scheme
+    UnsupportedScheme(String),
+    /// A bundle was created for a curve which is not supported
+    UnsupportedCurve(String),
+    /// A well-formed proof does not verify against its key
+    VerificationFailed,
+    /// A key and a proof are for different curves
+    CurveMismatch { vk: String, proof: String },
+    /// No value was provided for a public variable
+    MissingPublicInput(String),
+    /// A key or a proof could not be read from JSON
+    InvalidJson(String),
+    /// Arguments of `verifyTx` are not laid out for the ABI of the verifier, `found` is the ABI
+    /// they are laid out for, if any
+    AbiMismatch {
+        expected: SolidityAbi,
+        found: Option<SolidityAbi>,
+    },
+    /// The `raw` encoding of a proof could not be decoded
+    InvalidRaw(String),
+    /// The `raw` encoding of a proof holds another value for the point `point` than the proof points
+    RawMismatch { point: String },
+    /// A key or a proof was generated by a version of ZoKrates which is not supported, see
+    /// `check_producer_version`
+    IncompatibleVersion(String),
+    /// The public input at `index` of a proof is not the expected one, see
+    /// `G16::verify_with_expected_inputs`
+    UnexpectedPublicInput { index: usize },
+    /// The public input at `index` is given twice to `VerificationKey::precompute_fixed_inputs`
+    DuplicateFixedInput { index: usize },
+    /// The name of an exported verifier contract is not a Solidity identifier
+    InvalidContractName(String),
+    /// A name of `SolidityExportOptions::input_names` is not a Solidity identifier, is used twice
+    /// or is already used by `verifyTx`
+    InvalidInputName { name: String, reason: String },
+    /// The license identifier of an exported verifier is not an SPDX license expression
+    InvalidLicenseIdentifier(String),
+    /// A key in the encoding of bellman could not be read
+    InvalidBellmanKey(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::MissingPoint(point) => write!(f, "Missing point `{}`", point),
+            Error::InvalidPoint { point, reason } => {
+                write!(f, "Invalid point `{}`: {}", point, reason)
+            }
+            Error::InvalidPublicInput { value, reason } => {
+                write!(f, "Invalid public input `{}`: {}", value, reason)
+            }
+            Error::InputCountMismatch { expected, found } => {
+                write!(f, "Expected {} public inputs, found {}", expected, found)
+            }
+            Error::InvalidProvingKey(reason) => write!(f, "Invalid proving key: {}", reason),
+            Error::InvalidWordCount(count) => write!(
+                f,
+                "Invalid number of words for a verification key: {}",
+                count
+            ),
+            Error::InvalidPreparedKey(reason) => {
+                write!(f, "Invalid prepared verification key: {}", reason)
+            }
+            Error::UnsupportedScheme(scheme) => write!(
+                f,
+                "Unsupported proving scheme `{}`, expected `{}`",
+                scheme, G16_SCHEME
+            ),
+            Error::UnsupportedCurve(curve) => write!(f, "Unsupported curve `{}`", curve),
+            Error::VerificationFailed => write!(f, "Verification failed"),
+            Error::InvalidJson(reason) => write!(f, "Invalid JSON: {}", reason),
+            Error::MissingPublicInput(variable) => {
+                write!(f, "Missing value for public input `{}`", variable)
+            }
+            Error::CurveMismatch { vk, proof } => write!(
+                f,
+                "Verification key for curve `{}` cannot verify a proof for curve `{}`",
+                vk, proof
+            ),
+            Error::AbiMismatch { expected, found } => match found {
+                Some(found) => write!(
+                    f,
+                    "Arguments are laid out for the {:?} ABI, expected {:?}",
+                    found, expected
+                ),
+                None => write!(f, "Arguments are not laid out for the {:?} ABI", expected),
+            },
+            Error::InvalidRaw(reason) => write!(f, "Invalid raw proof: {}", reason),
+            Error::RawMismatch { point } => {
+                write!(f, "Raw proof does not match the proof point `{}`", point)
+            }
+            Error::IncompatibleVersion(reason) => write!(f, "Incompatible version: {}", reason),
+            Error::InvalidBellmanKey(reason) => {
+                write!(f, "Invalid bellman verification key: {}", reason)
+            }
+            Error::UnexpectedPublicInput { index } => {
+                write!(f, "Public input {} does not have the expected value", index)
+            }
+            Error::DuplicateFixedInput { index } => write!(f, "Input {} is fixed twice", index),
+            Error::InvalidContractName(name) => write!(
+                f,
+                "Invalid contract name `{}`, expected a Solidity identifier",
+                name
+            ),
+            Error::InvalidInputName { name, reason } => {
+                write!(f, "Invalid input name `{}`: {}", name, reason)
+            }
+            Error::InvalidLicenseIdentifier(identifier) => write!(
+                f,
+                "Invalid license identifier `{}`, expected an SPDX license expression",
+                identifier.escape_debug()
+            ),
+        }
+    }
+}
+
+fn to_eth_word(s: &str) -> [u8; 32] {
+    let bytes = BigUint::parse_bytes(s.trim_start_matches("0x").as_bytes(), 16)
+        .unwrap_or_else(|| panic!("Invalid coordinate `{}`", s))
+        .to_bytes_be();
+    assert!(bytes.len() <= 32, "Coordinate `{}` exceeds 32 bytes", s);
+
+    let mut word = [0u8; 32];
+    word[32 - bytes.len()..].copy_from_slice(&bytes);
+    word
+}
+
+/// Parses a public input with `parse_field_input`, like `Proof::public_inputs`
+fn parse_public_input<T: Field>(s: &str) -> Result<T, Error> {
+    parse_field_input(s).map_err(|reason| Error::InvalidPublicInput {
+        value: s.to_string(),
+        reason,
+    })
+}
+
+fn decode_g1<T: Field>(
+    name: &str,
+    g1: &G1Affine,
+) -> Result<<T::BellmanEngine as Engine>::G1Affine, Error> {
+    serialization::to_g1::<T>(g1.clone()).map_err(|reason| Error::InvalidPoint {
+        point: name.to_string(),
+        reason,
+    })
+}
+
+/// The order in which the two components `c0 + c1 * u` of each coordinate of a G2 point are
+/// written, see `G2Affine::in_math_order`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum G2Order {
+    /// `(c1, c0)`, as in ZoKrates keys and proofs, the Ethereum precompiles and the exported verifier
+    BellmanReversed,
+    /// `(c0, c1)`, as used by some other tools
+    Natural,
+}
+
+impl Default for G2Order {
+    fn default() -> Self {
+        G2Order::BellmanReversed
+    }
+}
+
+fn decode_g2<T: Field>(
+    name: &str,
+    g2: &G2Affine,
+    order: G2Order,
+) -> Result<<T::BellmanEngine as Engine>::G2Affine, Error> {
+    serialization::to_g2::<T>(g2.clone(), order).map_err(|reason| Error::InvalidPoint {
+        point: name.to_string(),
+        reason,
+    })
+}
+
+/// Rejects the point at infinity, which is not a valid value for the point `name`
+fn finite<P: CurveAffine>(name: &str, p: P) -> Result<P, Error> {
+    if p.is_zero() {
+        Err(Error::InvalidPoint {
+            point: name.to_string(),
+            reason: String::from("point at infinity"),
+        })
+    } else {
+        Ok(p)
+    }
+}
+
+/// Rejects points outside of the subgroup of prime order `r`, i.e. points `p` with `r * p != 0`,
+/// which decoding does not check
+fn in_subgroup<P: CurveAffine>(name: &str, p: P) -> Result<P, Error> {
+    if p.mul(P::Scalar::char()).is_zero() {
+        Ok(p)
+    } else {
+        Err(Error::InvalidPoint {
+            point: name.to_string(),
+            reason: String::from("not in the prime order subgroup"),
+        })
+    }
+}
+
+fn decode_finite_g1<T: Field>(
+    name: &str,
+    g1: &G1Affine,
+) -> Result<<T::BellmanEngine as Engine>::G1Affine, Error> {
+    decode_g1::<T>(name, g1).and_then(|p| finite(name, p))
+}
+
+fn decode_finite_g2<T: Field>(
+    name: &str,
+    g2: &G2Affine,
+    order: G2Order,
+) -> Result<<T::BellmanEngine as Engine>::G2Affine, Error> {
+    decode_g2::<T>(name, g2, order).and_then(|p| finite(name, p))
+}
+
+#[derive(Default)]
+pub struct VerificationKeyBuilder {
+    alpha: Option<G1Affine>,
+    beta: Option<G2Affine>,
+    gamma: Option<G2Affine>,
+    delta: Option<G2Affine>,
+    gamma_abc: Vec<G1Affine>,
+}
+
+impl VerificationKey {
+    pub fn builder() -> VerificationKeyBuilder {
+        VerificationKeyBuilder::default()
+    }
+}
+
+impl VerificationKeyBuilder {
+    pub fn alpha(mut self, alpha: G1Affine) -> Self {
+        self.alpha = Some(alpha);
+        self
+    }
+
+    pub fn beta(mut self, beta: G2Affine) -> Self {
+        self.beta = Some(beta);
+        self
+    }
+
+    pub fn gamma(mut self, gamma: G2Affine) -> Self {
+        self.gamma = Some(gamma);
+        self
+    }
+
+    pub fn delta(mut self, delta: G2Affine) -> Self {
+        self.delta = Some(delta);
+        self
+    }
+
+    pub fn gamma_abc(mut self, gamma_abc: Vec<G1Affine>) -> Self {
+        self.gamma_abc = gamma_abc;
+        self
+    }
+
+    /// Builds the key, checking that all points are provided and lie on the curve of `T`,
+    /// and that none but the `gamma_abc` points is the point at infinity
+    pub fn build<T: Field>(self) -> Result<VerificationKey, Error> {
+        let alpha = self
+            .alpha
+            .ok_or_else(|| Error::MissingPoint(String::from("alpha")))?;
+        let beta = self
+            .beta
+            .ok_or_else(|| Error::MissingPoint(String::from("beta")))?;
+        let gamma = self
+            .gamma
+            .ok_or_else(|| Error::MissingPoint(String::from("gamma")))?;
+        let delta = self
+            .delta
+            .ok_or_else(|| Error::MissingPoint(String::from("delta")))?;
+        if self.gamma_abc.is_empty() {
+            return Err(Error::MissingPoint(String::from("gamma_abc[0]")));
+        }
+
+        let vk = VerificationKey {
+            alpha,
+            beta,
+            gamma,
+            delta,
+            gamma_abc: self.gamma_abc,
+            producer_version: None,
+        };
+
+        vk.to_bellman::<T>()?;
+
+        Ok(vk)
+    }
+}
+
+#[derive(Default)]
+pub struct ProofPointsBuilder {
+    a: Option<G1Affine>,
+    b: Option<G2Affine>,
+    c: Option<G1Affine>,
+}
+
+impl ProofPoints {
+    pub fn builder() -> ProofPointsBuilder {
+        ProofPointsBuilder::default()
+    }
+}
+
+impl ProofPointsBuilder {
+    pub fn a(mut self, a: G1Affine) -> Self {
+        self.a = Some(a);
+        self
+    }
+
+    pub fn b(mut self, b: G2Affine) -> Self {
+        self.b = Some(b);
+        self
+    }
+
+    pub fn c(mut self, c: G1Affine) -> Self {
+        self.c = Some(c);
+        self
+    }
+
+    /// Builds the proof points, checking that all points are provided, lie on the curve of `T`
+    /// and are not the point at infinity
+    pub fn build<T: Field>(self) -> Result<ProofPoints, Error> {
+        let a = self
+            .a
+            .ok_or_else(|| Error::MissingPoint(String::from("a")))?;
+        let b = self
+            .b
+            .ok_or_else(|| Error::MissingPoint(String::from("b")))?;
+        let c = self
+            .c
+            .ok_or_else(|| Error::MissingPoint(String::from("c")))?;
+
+        let proof = ProofPoints { a, b, c };
+
+        proof.to_bellman::<T>()?;
+
+        Ok(proof)
+    }
+}
+
+impl<T: Field> ProofSystem<T> for G16 {
+    type VerificationKey = VerificationKey;
+    type ProofPoints = ProofPoints;
+
+    fn setup(program: ir::Prog<T>) -> SetupKeypair<VerificationKey> {
+        G16::setup_writing_warning(program, None, &mut PrintOut)
+    }
+
+    fn generate_proof(
+        program: ir::Prog<T>,
+        witness: ir::Witness<T>,
+        proving_key: Vec<u8>,
+    ) -> Proof<ProofPoints> {
+        G16::generate_proof_writing_warning(program, witness, proving_key, None, &mut PrintOut)
+    }
+
+    fn export_solidity_verifier(vk: VerificationKey, abi: SolidityAbi) -> String {
+        G16::export_solidity_verifier_with_options(vk, abi, &SolidityExportOptions::default())
+    }
+
+    fn verify(vk: VerificationKey, proof: Proof<ProofPoints>) -> bool {
+        G16::verify_ref::<T>(&vk, &proof).unwrap_or(false)
+    }
+}
+
+impl G16 {
+    /// Acknowledges that G16 proofs are malleable, see `G16_WARNING`, so that the warning is not
+    /// printed by `setup_acknowledged` and `generate_proof_acknowledged`
+    pub fn acknowledge_malleability() -> MalleabilityAcknowledged {
+        MalleabilityAcknowledged(())
+    }
+
+    /// Runs the setup like `ProofSystem::setup` without printing the malleability warning
+    pub fn setup_acknowledged<T: Field>(
+        program: ir::Prog<T>,
+        acknowledged: MalleabilityAcknowledged,
+    ) -> SetupKeypair<VerificationKey> {
+        G16::setup_writing_warning(program, Some(acknowledged), &mut PrintOut)
+    }
+
+    /// Generates a proof like `ProofSystem::generate_proof` without printing the malleability warning
+    pub fn generate_proof_acknowledged<T: Field>(
+        program: ir::Prog<T>,
+        witness: ir::Witness<T>,
+        proving_key: Vec<u8>,
+        acknowledged: MalleabilityAcknowledged,
+    ) -> Proof<ProofPoints> {
+        G16::generate_proof_writing_warning(
+            program,
+            witness,
+            proving_key,
+            Some(acknowledged),
+            &mut PrintOut,
+        )
+    }
+
+    /// Runs the setup like `ProofSystem::setup`, writing the malleability warning to `out` unless
+    /// it is acknowledged
+    pub fn setup_writing_warning<T: Field, W: Write>(
+        program: ir::Prog<T>,
+        acknowledged: Option<MalleabilityAcknowledged>,
+        out: &mut W,
+    ) -> SetupKeypair<VerificationKey> {
+        keypair_from_parameters::<T>(setup_parameters(program, acknowledged, out))
+    }
+
+    /// Generates a proof like `ProofSystem::generate_proof`, writing the malleability warning to
+    /// `out` unless it is acknowledged
+    pub fn generate_proof_writing_warning<T: Field, W: Write>(
+        program: ir::Prog<T>,
+        witness: ir::Witness<T>,
+        proving_key: Vec<u8>,
+        acknowledged: Option<MalleabilityAcknowledged>,
+        out: &mut W,
+    ) -> Proof<ProofPoints> {
+        let (computation, params) =
+            prepare_proof(program, witness, &proving_key, acknowledged, out);
+
+        let proof = computation.clone().prove(&params);
+
+        proof_from_bellman(&computation, &proof)
+    }
+}
+
+impl G16 {
+    /// Checks the versions of ZoKrates which generated `vk` and `proof`, see
+    /// `check_producer_version`. Verification ignores the versions unless
+    /// `VerifyOptions::strict_version` is set, so callers which want to warn about incompatible
+    /// versions check them with this.
+    pub fn check_producer_versions(
+        vk: &VerificationKey,
+        proof: &Proof<ProofPoints>,
+    ) -> Result<(), Error> {
+        check_producer_version(vk.producer_version())
+            .and_then(|_| check_producer_version(proof.producer_version.as_deref()))
+            .map_err(Error::IncompatibleVersion)
+    }
+}
+
+/// The standard output, written with `print!` so that the output of tests is captured, which is
+/// not the case for `io::stdout()`
+struct PrintOut;
+
+impl Write for PrintOut {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        print!("{}", String::from_utf8_lossy(buf));
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        io::stdout().flush()
+    }
+}
+
+fn write_malleability_warning<W: Write>(
+    acknowledged: Option<MalleabilityAcknowledged>,
+    out: &mut W,
+) {
+    if acknowledged.is_none() {
+        writeln!(out, "{}", G16_WARNING).unwrap();
+    }
+}
+
+/// The setup shared by all setup functions: panics if `program` is malformed, silences bellman,
+/// writes the malleability warning unless it is acknowledged and generates the parameters
+fn setup_parameters<T: Field, W: Write>(
+    program: ir::Prog<T>,
+    acknowledged: Option<MalleabilityAcknowledged>,
+    out: &mut W,
+) -> Parameters<T::BellmanEngine> {
+    if let Err(e) = program.validate() {
+        panic!("Cannot run the setup for a malformed program: {}", e);
+    }
+
+    prepare_bellman(acknowledged, out);
+
+    Computation::without_witness(program).setup()
+}
+
+/// Runs before each proof generation: silences bellman, writes the malleability warning unless it
+/// is acknowledged and reads the proving key
+fn prepare_proof<T: Field, W: Write>(
+    program: ir::Prog<T>,
+    witness: ir::Witness<T>,
+    proving_key: &[u8],
+    acknowledged: Option<MalleabilityAcknowledged>,
+    out: &mut W,
+) -> (Computation<T>, Parameters<T::BellmanEngine>) {
+    prepare_bellman(acknowledged, out);
+
+    let computation = Computation::with_witness(program, witness);
+    let params = read_proving_key::<T>(proving_key).unwrap();
+
+    (computation, params)
+}
+
+/// Silences bellman and writes the malleability warning unless it is acknowledged
+fn prepare_bellman<W: Write>(acknowledged: Option<MalleabilityAcknowledged>, out: &mut W) {
+    #[cfg(not(target_arch = "wasm32"))]
+    std::env::set_var("BELLMAN_VERBOSE", "0");
+
+    write_malleability_warning(acknowledged, out);
+}
+
+impl G16 {
+    /// Runs the setup and exports the Solidity verifier for the resulting verification key
+    pub fn setup_and_export_solidity<T: Field>(
+        program: ir::Prog<T>,
+        abi: SolidityAbi,
+    ) -> (SetupKeypair<VerificationKey>, String) {
+        let keypair = <G16 as ProofSystem<T>>::setup(program);
+        let verifier = <G16 as ProofSystem<T>>::export_solidity_verifier(keypair.vk.clone(), abi);
+
+        (keypair, verifier)
+    }
+
+    /// Reads the verification key embedded in a proving key returned by `ProofSystem::setup`
+    pub fn extract_vk_from_pk<T: Field>(proving_key: &[u8]) -> Result<VerificationKey, Error> {
+        let parameters = read_proving_key::<T>(proving_key)
+            .map_err(|e| Error::InvalidProvingKey(e.to_string()))?;
+
+        Ok(VerificationKey::from_bellman::<T>(&parameters.vk))
+    }
+
+    /// Runs the setup like `ProofSystem::setup`, returning only the verification key. Bellman
+    /// still generates the full parameters, but the proving key is dropped instead of being
+    /// serialized, which saves the time and memory of its encoding. The malleability warning is
+    /// written to `out` unless it is acknowledged.
+    pub fn setup_vk_only<T: Field, W: Write>(
+        program: ir::Prog<T>,
+        acknowledged: Option<MalleabilityAcknowledged>,
+        out: &mut W,
+    ) -> VerificationKey {
+        let parameters = setup_parameters(program, acknowledged, out);
+
+        VerificationKey::from_setup::<T>(&parameters.vk)
+    }
+}
+
+/// The encoding of the points of a proving key written by `G16::setup_with_compression`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KeyCompression {
+    /// the encoding of bellman, as written by `ProofSystem::setup`
+    Uncompressed,
+    /// compressed points behind a header, about half the size of the uncompressed key but
+    /// slower to read, as each point is decompressed
+    Compressed,
+}
+
+impl Default for KeyCompression {
+    fn default() -> Self {
+        KeyCompression::Uncompressed
+    }
+}
+
+/// The header of a compressed proving key. The first byte has both flag bits of a point set, which
+/// the first point of an uncompressed key never has, so that both encodings can be told apart.
+const COMPRESSED_PK_HEADER: &[u8] = b"\xffZKC";
+
+impl G16 {
+    /// Runs the setup like `ProofSystem::setup`, writing the points of the proving key with the
+    /// given `compression`. Proving keys of both encodings are accepted when generating a proof.
+    /// The malleability warning is written to `out` unless it is acknowledged.
+    pub fn setup_with_compression<T: Field, W: Write>(
+        program: ir::Prog<T>,
+        compression: KeyCompression,
+        acknowledged: Option<MalleabilityAcknowledged>,
+        out: &mut W,
+    ) -> SetupKeypair<VerificationKey> {
+        let parameters = setup_parameters(program, acknowledged, out);
+
+        let vk = VerificationKey::from_setup::<T>(&parameters.vk);
+        let pk = write_proving_key::<T>(&parameters, compression);
+
+        SetupKeypair::new(vk, pk)
+    }
+}
+
+impl G16 {
+    /// Generates a proof like `ProofSystem::generate_proof`, drawing its randomness from `rng`.
+    /// Outside of tests, `rng` must be a cryptographically secure generator which is seeded
+    /// unpredictably, see `Computation::prove_with_rng`. The malleability warning is written to
+    /// `out` unless it is acknowledged.
+    pub fn generate_proof_with_rng<T: Field, R: Rng, W: Write>(
+        program: ir::Prog<T>,
+        witness: ir::Witness<T>,
+        proving_key: Vec<u8>,
+        rng: &mut R,
+        acknowledged: Option<MalleabilityAcknowledged>,
+        out: &mut W,
+    ) -> Proof<ProofPoints> {
+        let (computation, params) =
+            prepare_proof(program, witness, &proving_key, acknowledged, out);
+
+        let proof = computation.clone().prove_with_rng(&params, rng);
+
+        proof_from_bellman(&computation, &proof)
+    }
+}
+
+/// A description of a setup, returned by `G16::setup_with_report` so that callers can decide how
+/// to present it
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct SetupReport {
+    pub constraint_count: usize,
+    /// the size of the proving key, in bytes
+    pub pk_size: usize,
+    /// the number of `gamma_abc` points of the verification key, one more than the number of public inputs
+    pub gamma_abc_len: usize,
+    /// warnings about the scheme, which `ProofSystem::setup` prints instead
+    pub warnings: Vec<String>,
+}
+
+impl G16 {
+    /// Runs the setup like `ProofSystem::setup` without printing anything, returning the warnings
+    /// and the size of the output in a `SetupReport` instead
+    pub fn setup_with_report<T: Field>(
+        program: ir::Prog<T>,
+    ) -> (SetupKeypair<VerificationKey>, SetupReport) {
+        let constraint_count = program.constraint_count();
+
+        // the warning is returned in the report
+        let parameters = setup_parameters(
+            program,
+            Some(G16::acknowledge_malleability()),
+            &mut io::sink(),
+        );
+        let keypair = keypair_from_parameters::<T>(parameters);
+
+        let report = SetupReport {
+            constraint_count,
+            pk_size: keypair.pk.len(),
+            gamma_abc_len: keypair.vk.gamma_abc.len(),
+            warnings: vec![G16_WARNING.to_string()],
+        };
+
+        (keypair, report)
+    }
+}
+
+impl SetupKeypair<VerificationKey> {
+    /// A keccak256 digest of the constraints of `program`, the proving key and the `to_eth_words` of
+    /// the verification key, which can be published after a setup so that anyone holding the three
+    /// can check that they belong together
+    pub fn provenance_digest<T: Field>(&self, program: &ir::Prog<T>) -> [u8; 32] {
+        let constraints = constraints_encoding(program);
+
+        let mut hasher = Keccak256::new();
+        // each part is prefixed with its length so that bytes cannot move from one part to another
+        hasher.input(&(constraints.len() as u64).to_be_bytes());
+        hasher.input(&constraints);
+        hasher.input(&(self.pk.len() as u64).to_be_bytes());
+        hasher.input(&self.pk);
+        for word in self.vk.to_eth_words() {
+            hasher.input(&word[..]);
+        }
+
+        let mut digest = [0u8; 32];
+        digest.copy_from_slice(hasher.result().as_slice());
+        digest
+    }
+}
+
+/// Encodes the constraints of a program as one line per constraint, listing the terms of the
+/// canonical linear combinations `a`, `b` and `c` of `a * b == c`, so that programs which only
+/// differ in the order of the terms have the same encoding
+fn constraints_encoding<T: Field>(program: &ir::Prog<T>) -> Vec<u8> {
+    fn terms<T: Field>(l: &ir::LinComb<T>) -> String {
+        l.clone()
+            .into_canonical()
+            .0
+            .into_iter()
+            .map(|(variable, coefficient)| format!("{}:{};", variable, coefficient))
+            .collect()
+    }
+
+    program
+        .main
+        .statements
+        .iter()
+        .filter_map(|s| match s {
+            ir::Statement::Constraint(quad, lin) => Some(format!(
+                "{}|{}|{}\n",
+                terms(&quad.left),
+                terms(&quad.right),
+                terms(lin)
+            )),
+            ir::Statement::Directive(..) => None,
+        })
+        .collect::<String>()
+        .into_bytes()
+}
+
+/// The stages of a setup, reported by `G16::setup_with_progress`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SetupStage {
+    CountingConstraints,
+    GeneratingParameters,
+    WritingKey,
+}
+
+impl G16 {
+    /// Runs the setup like `ProofSystem::setup`, calling `progress` with the stage which starts and
+    /// the number of stages which are done out of the total, and a last time once the setup is done.
+    /// Bellman does not report its progress, so generating the parameters is a single step.
+    /// The malleability warning is written to `out` unless it is acknowledged.
+    pub fn setup_with_progress<T: Field, F: FnMut(SetupStage, usize, usize), W: Write>(
+        program: ir::Prog<T>,
+        mut progress: F,
+        acknowledged: Option<MalleabilityAcknowledged>,
+        out: &mut W,
+    ) -> SetupKeypair<VerificationKey> {
+        const STAGES: usize = 3;
+
+        progress(SetupStage::CountingConstraints, 0, STAGES);
+
+        progress(SetupStage::GeneratingParameters, 1, STAGES);
+        let parameters = setup_parameters(program, acknowledged, out);
+
+        progress(SetupStage::WritingKey, 2, STAGES);
+        let keypair = keypair_from_parameters::<T>(parameters);
+
+        progress(SetupStage::WritingKey, STAGES, STAGES);
+        keypair
+    }
+}
+
+/// Wall-clock durations of the phases of a setup or a proof generation, in microseconds.
+/// Phases which are not part of the measured operation are `None`.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Timings {
+    /// running the setup, including the synthesis of the circuit
+    pub setup_us: Option<u128>,
+    /// reading the proving key
+    pub read_key_us: Option<u128>,
+    /// generating the proof, including the synthesis of the circuit
+    pub prove_us: Option<u128>,
+}
+
+/// Wall-clock durations of the phases of `G16::verify_timed`, in microseconds
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct VerifyTimings {
+    /// decoding the public inputs, the proof and the verification key into curve points
+    pub parse_us: u128,
+    /// preparing the verification key, which computes the pairing of `alpha` and `beta`
+    pub prepare_us: u128,
+    /// the pairing check of the proof
+    pub pairing_us: u128,
+}
+
+impl G16 {
+    /// Runs the setup like `ProofSystem::setup`, also returning how long it took. The malleability
+    /// warning is written to `out` unless it is acknowledged.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn setup_timed<T: Field, W: Write>(
+        program: ir::Prog<T>,
+        acknowledged: Option<MalleabilityAcknowledged>,
+        out: &mut W,
+    ) -> (SetupKeypair<VerificationKey>, Timings) {
+        let start = Instant::now();
+        let parameters = setup_parameters(program, acknowledged, out);
+        let setup_us = start.elapsed().as_micros();
+
+        (
+            keypair_from_parameters::<T>(parameters),
+            Timings {
+                setup_us: Some(setup_us),
+                ..Timings::default()
+            },
+        )
+    }
+
+    /// Generates a proof like `ProofSystem::generate_proof`, also returning how long each phase took.
+    /// The malleability warning is written to `out` unless it is acknowledged.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn generate_proof_timed<T: Field, W: Write>(
+        program: ir::Prog<T>,
+        witness: ir::Witness<T>,
+        proving_key: Vec<u8>,
+        acknowledged: Option<MalleabilityAcknowledged>,
+        out: &mut W,
+    ) -> (Proof<ProofPoints>, Timings) {
+        let start = Instant::now();
+        let (computation, params) =
+            prepare_proof(program, witness, &proving_key, acknowledged, out);
+        let read_key_us = start.elapsed().as_micros();
+
+        let start = Instant::now();
+        let proof = computation.clone().prove(&params);
+        let prove_us = start.elapsed().as_micros();
+
+        (
+            proof_from_bellman(&computation, &proof),
+            Timings {
+                read_key_us: Some(read_key_us),
+                prove_us: Some(prove_us),
+                ..Timings::default()
+            },
+        )
+    }
+
+    /// Verifies a proof like `ProofSystem::verify`, also returning how long each phase took.
+    /// If decoding fails, the verification fails and the phases which did not run are left at zero.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn verify_timed<T: Field>(
+        vk: VerificationKey,
+        proof: Proof<ProofPoints>,
+    ) -> (bool, VerifyTimings) {
+        let mut timings = VerifyTimings::default();
+
+        let start = Instant::now();
+        let parsed = proof.validate_against_vk::<T>(&vk).and_then(|_| {
+            let inputs = proof
+                .inputs
+                .iter()
+                .map(|i| parse_public_input::<T>(i))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok((
+                vk.to_bellman::<T>()?,
+                proof.proof.into_bellman::<T>()?,
+                inputs,
+            ))
+        });
+        timings.parse_us = start.elapsed().as_micros();
+
+        let (vk, proof, inputs) = match parsed {
+            Ok(parsed) => parsed,
+            Err(_) => return (false, timings),
+        };
+
+        let start = Instant::now();
+        let pvk = PreparedVerificationKey::<T>::from_bellman(&vk);
+        timings.prepare_us = start.elapsed().as_micros();
+
+        let start = Instant::now();
+        let verified =
+            verify_bellman(&pvk, &proof, inputs.into_iter().map(Ok::<_, ()>)).unwrap_or(false);
+        timings.pairing_us = start.elapsed().as_micros();
+
+        (verified, timings)
+    }
+
+    /// Verifies a proof like `ProofSystem::verify`, also returning the wall-clock duration of the
+    /// whole verification in microseconds, so that its latency can be tracked over time. See the
+    /// `verify` benchmarks for a statistically meaningful measurement.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn bench_verify_once<T: Field>(
+        vk: VerificationKey,
+        proof: Proof<ProofPoints>,
+    ) -> (bool, u128) {
+        let start = Instant::now();
+        let verified = <G16 as ProofSystem<T>>::verify(vk, proof);
+
+        (verified, start.elapsed().as_micros())
+    }
+}
+
+/// A proof together with the key to verify it, so that a verifiable proof can be shared as a single file
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct VerifiableBundle {
+    pub scheme: String,
+    pub curve: String,
+    pub vk: VerificationKey,
+    pub proof: Proof<ProofPoints>,
+}
+
+impl VerifiableBundle {
+    pub fn new<T: Field>(vk: VerificationKey, proof: Proof<ProofPoints>) -> Self {
+        VerifiableBundle {
+            scheme: G16_SCHEME.to_string(),
+            curve: T::name().to_string(),
+            vk,
+            proof,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    /// Reads a bundle from JSON. Whether its key and its proof were generated by a compatible
+    /// version of ZoKrates is not checked, see `check_producer_versions`.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Checks the versions of ZoKrates which generated the key and the proof, see
+    /// `G16::check_producer_versions`
+    pub fn check_producer_versions(&self) -> Result<(), Error> {
+        G16::check_producer_versions(&self.vk, &self.proof)
+    }
+
+    /// Verifies the proof against the key on the curve named in the bundle. Fails if the bundle
+    /// is not a G16 bundle, if its curve is not supported, or if the key, the proof and its public
+    /// inputs are not valid on that curve.
+    pub fn verify(&self) -> Result<bool, Error> {
+        if self.scheme != G16_SCHEME {
+            return Err(Error::UnsupportedScheme(self.scheme.clone()));
+        }
+
+        struct VerifyOn<'a>(&'a VerifiableBundle);
+
+        impl<'a> FieldVisitor for VerifyOn<'a> {
+            type Output = Result<bool, Error>;
+
+            fn visit<T: Field>(self) -> Self::Output {
+                self.0.verify_on::<T>()
+            }
+        }
+
+        visit_field(&self.curve, VerifyOn(self))
+            .unwrap_or_else(|| Err(Error::UnsupportedCurve(self.curve.clone())))
+    }
+
+    fn verify_on<T: Field>(&self) -> Result<bool, Error> {
+        self.proof.validate_against_vk::<T>(&self.vk)?;
+
+        let inputs = self
+            .proof
+            .inputs
+            .iter()
+            .map(|i| parse_public_input::<T>(i))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        G16::verify_core(&self.vk, &self.proof.proof, &inputs)
+    }
+}
+
+/// A verification key tagged with its curve, for callers which handle several curves at runtime
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnyVerificationKey {
+    Bls12(VerificationKey),
+    Bn128(VerificationKey),
+}
+
+/// A proof tagged with its curve, see `AnyVerificationKey`
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnyProof {
+    Bls12(Proof<ProofPoints>),
+    Bn128(Proof<ProofPoints>),
+}
+
+impl AnyVerificationKey {
+    pub fn curve(&self) -> &'static str {
+        match self {
+            AnyVerificationKey::Bls12(_) => Bls12Field::name(),
+            AnyVerificationKey::Bn128(_) => Bn128Field::name(),
+        }
+    }
+}
+
+impl AnyProof {
+    pub fn curve(&self) -> &'static str {
+        match self {
+            AnyProof::Bls12(_) => Bls12Field::name(),
+            AnyProof::Bn128(_) => Bn128Field::name(),
+        }
+    }
+}
+
+impl G16 {
+    /// Verifies a proof like `ProofSystem::verify` on the curve of the key and the proof, which
+    /// must be the same
+    pub fn verify_any(vk: AnyVerificationKey, proof: AnyProof) -> Result<bool, Error> {
+        match (vk, proof) {
+            (AnyVerificationKey::Bls12(vk), AnyProof::Bls12(proof)) => {
+                Ok(<G16 as ProofSystem<Bls12Field>>::verify(vk, proof))
+            }
+            (AnyVerificationKey::Bn128(vk), AnyProof::Bn128(proof)) => {
+                Ok(<G16 as ProofSystem<Bn128Field>>::verify(vk, proof))
+            }
+            (vk, proof) => Err(Error::CurveMismatch {
+                vk: vk.curve().to_string(),
+                proof: proof.curve().to_string(),
+            }),
+        }
+    }
+}
+
+impl G16 {
+    /// The size in bytes of the JSON of a proof with `public_input_count` public inputs on the curve
+    /// named `curve`, as written by `Proof::to_json_pretty`. Proofs generated by ZoKrates always have
+    /// this size, since their coordinates, inputs and `raw` encoding are zero-padded to a fixed width.
+    pub fn expected_proof_json_size(
+        public_input_count: usize,
+        curve: &str,
+    ) -> Result<usize, Error> {
+        struct SizeOn(usize);
+
+        impl FieldVisitor for SizeOn {
+            type Output = usize;
+
+            fn visit<T: Field>(self) -> usize {
+                let proof = BellmanProof::<T::BellmanEngine> {
+                    a: CurveAffine::one(),
+                    b: CurveAffine::one(),
+                    c: CurveAffine::one(),
+                };
+
+                Proof::new(
+                    ProofPoints {
+                        a: parse_g1::<T>(&proof.a),
+                        b: parse_g2::<T>(&proof.b),
+                        c: parse_g1::<T>(&proof.c),
+                    },
+                    vec![parse_fr::<T>(&T::one().into_bellman()); self.0],
+                    serialize_raw::<T>(&proof),
+                )
+                .to_json_pretty()
+                .len()
+            }
+        }
+
+        visit_field(curve, SizeOn(public_input_count))
+            .ok_or_else(|| Error::UnsupportedCurve(curve.to_string()))
+    }
+}
+
+fn keypair_from_parameters<T: Field>(
+    parameters: Parameters<T::BellmanEngine>,
+) -> SetupKeypair<VerificationKey> {
+    let pk = write_proving_key::<T>(&parameters, KeyCompression::Uncompressed);
+
+    let vk = VerificationKey::from_setup::<T>(&parameters.vk);
+
+    SetupKeypair::new(vk, pk)
+}
+
+fn write_proving_key<T: Field>(
+    parameters: &Parameters<T::BellmanEngine>,
+    compression: KeyCompression,
+) -> Vec<u8> {
+    let mut pk: Vec<u8> = Vec::new();
+
+    match compression {
+        KeyCompression::Uncompressed => parameters.write(&mut pk).unwrap(),
+        KeyCompression::Compressed => {
+            let vk = &parameters.vk;
+
+            pk.extend_from_slice(COMPRESSED_PK_HEADER);
+            write_compressed(&mut pk, &[vk.alpha_g1, vk.beta_g1, vk.delta_g1]);
+            write_compressed(&mut pk, &[vk.beta_g2, vk.gamma_g2, vk.delta_g2]);
+            write_compressed(&mut pk, &vk.ic);
+            write_compressed(&mut pk, &parameters.h);
+            write_compressed(&mut pk, &parameters.l);
+            write_compressed(&mut pk, &parameters.a);
+            write_compressed(&mut pk, &parameters.b_g1);
+            write_compressed(&mut pk, &parameters.b_g2);
+        }
+    }
+
+    pk
+}
+
+/// Reads a proving key written with any `KeyCompression`, detecting the encoding from its header
+fn read_proving_key<T: Field>(proving_key: &[u8]) -> io::Result<Parameters<T::BellmanEngine>> {
+    if !proving_key.starts_with(COMPRESSED_PK_HEADER) {
+        return Parameters::read(proving_key, true);
+    }
+
+    let mut reader = &proving_key[COMPRESSED_PK_HEADER.len()..];
+
+    let g1 = read_compressed(&mut reader)?;
+    let g2 = read_compressed(&mut reader)?;
+    if g1.len() != 3 || g2.len() != 3 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "unexpected number of verification key points",
+        ));
+    }
+
+    let vk = VerifyingKey {
+        alpha_g1: g1[0],
+        beta_g1: g1[1],
+        beta_g2: g2[0],
+        gamma_g2: g2[1],
+        delta_g1: g1[2],
+        delta_g2: g2[2],
+        ic: read_compressed(&mut reader)?,
+    };
+
+    let h = read_compressed(&mut reader)?;
+    let l = read_compressed(&mut reader)?;
+    let a = read_compressed(&mut reader)?;
+    let b_g1 = read_compressed(&mut reader)?;
+    let b_g2 = read_compressed(&mut reader)?;
+
+    if !reader.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "trailing bytes after the proving key",
+        ));
+    }
+
+    Ok(Parameters {
+        vk,
+        h: Arc::new(h),
+        l: Arc::new(l),
+        a: Arc::new(a),
+        b_g1: Arc::new(b_g1),
+        b_g2: Arc::new(b_g2),
+    })
+}
+
+/// Writes the number of `points` as a big endian `u32`, followed by the compressed points
+fn write_compressed<G: CurveAffine>(out: &mut Vec<u8>, points: &[G]) {
+    out.extend_from_slice(&(points.len() as u32).to_be_bytes());
+    for p in points {
+        out.extend_from_slice(p.into_compressed().as_ref());
+    }
+}
+
+fn read_compressed<G: CurveAffine, R: Read>(reader: &mut R) -> io::Result<Vec<G>> {
+    let mut len = [0u8; 4];
+    reader.read_exact(&mut len)?;
+
+    (0..u32::from_be_bytes(len))
+        .map(|_| {
+            let mut repr = G::Compressed::empty();
+            reader.read_exact(repr.as_mut())?;
+            repr.into_affine()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        })
+        .collect()
+}
+
+fn proof_from_bellman<T: Field>(
+    computation: &Computation<T>,
+    proof: &BellmanProof<T::BellmanEngine>,
+) -> Proof<ProofPoints> {
+    let proof_points = ProofPoints::from_bellman::<T>(proof);
+
+    let inputs = computation
+        .public_inputs_values()
+        .iter()
+        .map(parse_fr::<T>)
+        .collect::<Vec<_>>();
+
+    Proof::<ProofPoints>::new(proof_points, inputs, serialize_raw::<T>(proof))
+}
+
+fn serialize_raw<T: Field>(proof: &BellmanProof<T::BellmanEngine>) -> String {
+    let mut raw: Vec<u8> = Vec::new();
+    proof.write(&mut raw).unwrap();
+
+    hex::encode(&raw)
+}
+
+mod serialization {
+    use super::G2Order;
+    use pairing::ff::{PrimeField, ScalarEngine};
+    use pairing::{from_hex, to_hex, CurveAffine, Engine};
+    use proof_system::{G1Affine, G2Affine};
+    use zokrates_field::Field;
+
+    /// Checks that `s` is exactly the representation `to_hex` gives of the element it decodes to
+    fn is_canonical<F: PrimeField>(s: &str) -> bool {
+        from_hex::<F>(s)
+            .map(|e| format!("0x{}", to_hex(&e)) == s)
+            .unwrap_or(false)
+    }
+
+    pub fn is_canonical_fr<T: Field>(s: &str) -> bool {
+        is_canonical::<<T::BellmanEngine as ScalarEngine>::Fr>(s)
+    }
+
+    pub fn is_canonical_g1<T: Field>(g1: &G1Affine) -> bool {
+        is_canonical::<<T::BellmanEngine as Engine>::Fq>(&g1.0)
+            && is_canonical::<<T::BellmanEngine as Engine>::Fq>(&g1.1)
+    }
+
+    pub fn is_canonical_g2<T: Field>(g2: &G2Affine) -> bool {
+        is_canonical_g1::<T>(&g2.0) && is_canonical_g1::<T>(&g2.1)
+    }
+
+    pub fn to_g1<T: Field>(g1: G1Affine) -> Result<<T::BellmanEngine as Engine>::G1Affine, String> {
+        <T::BellmanEngine as Engine>::G1Affine::from_xy_checked(from_hex(&g1.0)?, from_hex(&g1.1)?)
+            .map_err(|e| e.to_string())
+    }
+    pub fn to_g2<T: Field>(
+        g2: G2Affine,
+        order: G2Order,
+    ) -> Result<<T::BellmanEngine as Engine>::G2Affine, String> {
+        // `new_fq2` panics on invalid components, so decode them first
+        for c in &[&(g2.0).0, &(g2.0).1, &(g2.1).0, &(g2.1).1] {
+            from_hex::<<T::BellmanEngine as Engine>::Fq>(c)?;
+        }
+        let (x, y) = match order {
+            G2Order::BellmanReversed => (
+                T::new_fq2(&(g2.0).1, &(g2.0).0),
+                T::new_fq2(&(g2.1).1, &(g2.1).0),
+            ),
+            G2Order::Natural => (
+                T::new_fq2(&(g2.0).0, &(g2.0).1),
+                T::new_fq2(&(g2.1).0, &(g2.1).1),
+            ),
+        };
+        <T::BellmanEngine as Engine>::G2Affine::from_xy_checked(x, y).map_err(|e| e.to_string())
+    }
+}
+
+/// Runs the setup for `identity_program` and generates a proof for the argument `x`, whose
+/// public inputs are `[x, x]`
+#[cfg(test)]
+fn setup_and_prove(x: i32) -> (SetupKeypair<VerificationKey>, Proof<ProofPoints>) {
+    use crate::ir::Interpreter;
+    use crate::proof_system::identity_program;
+
+    let program = identity_program();
+    let keypair = G16::setup(program.clone());
+    let witness = Interpreter::default()
+        .execute(&program, &vec![Bn128Field::from(x)])
+        .unwrap();
+    let proof = G16::generate_proof(program, witness, keypair.pk.clone());
+
+    (keypair, proof)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::flat_absy::FlatVariable;
+    use crate::ir::{Function, Interpreter, LinComb, Prog, QuadComb, Statement};
+    use crate::proof_system::bellman::rand::{ChaChaRng, SeedableRng};
+    use crate::proof_system::identity_program;
+
+    use super::*;
+    use zokrates_field::{Bls12Field, Bn128Field};
+
+    #[test]
+    fn verify() {
+        let program: Prog<Bn128Field> = Prog {
+            main: Function {
+                id: String::from("main"),
+                arguments: vec![FlatVariable::new(0)],
+                returns: vec![FlatVariable::public(0)],
+                statements: vec![Statement::Constraint(
+                    FlatVariable::new(0).into(),
+                    FlatVariable::public(0).into(),
+                )],
+            },
+            private: vec![false],
+        };
+
+        let keypair = G16::setup(program.clone());
+
+        let interpreter = Interpreter::default();
+
+        let witness = interpreter
+            .execute(&program, &vec![Bn128Field::from(42)])
+            .unwrap();
+
+        let proof = G16::generate_proof(program, witness, keypair.pk);
+        let ans = <G16 as ProofSystem<Bn128Field>>::verify(keypair.vk, proof);
+
+        assert!(ans);
+    }
+
+    #[test]
+    fn verify_unprefixed_input() {
+        use crate::proof_system::PublicInputs;
+
+        let (keypair, mut proof) = setup_and_prove(10);
+
+        // inputs without prefix are decimal, both when reading and when verifying them
+        proof.inputs = vec![String::from("10"), String::from("10")];
+        assert_eq!(
+            proof.public_inputs::<Bn128Field>(),
+            Ok(PublicInputs(vec![
+                Bn128Field::from(10),
+                Bn128Field::from(10)
+            ]))
+        );
+        assert_eq!(G16::verify_ref::<Bn128Field>(&keypair.vk, &proof), Ok(true));
+
+        proof.inputs = vec![String::from("16"), String::from("0x10")];
+        assert_eq!(
+            proof.public_inputs::<Bn128Field>(),
+            Ok(PublicInputs(vec![
+                Bn128Field::from(16),
+                Bn128Field::from(16)
+            ]))
+        );
+        assert_eq!(
+            G16::verify_ref::<Bn128Field>(&keypair.vk, &proof),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn verify_negative_input() {
+        let program = identity_program();
+
+        let keypair = G16::setup(program.clone());
+
+        let interpreter = Interpreter::default();
+
+        let witness = interpreter
+            .execute(&program, &vec![Bn128Field::from(-1)])
+            .unwrap();
+
+        let proof = G16::generate_proof(program.clone(), witness, keypair.pk.clone());
+        assert_eq!(
+            proof.inputs[0],
+            format!("0x{:064x}", Bn128Field::modulus() - 1u32)
+        );
+
+        let vk = keypair.vk;
+        let verify = |input: &str| {
+            let mut proof = proof.clone();
+            proof.inputs[0] = input.to_string();
+            <G16 as ProofSystem<Bn128Field>>::verify(vk.clone(), proof)
+        };
+
+        assert!(verify(&proof.inputs[0]));
+        assert_eq!(verify("-1"), verify(&proof.inputs[0]));
+        assert!(verify("-0x1"));
+        assert!(!verify("-2"));
+        assert!(!verify("-"));
+
+        assert_eq!(
+            parse_public_input::<Bn128Field>("-0"),
+            Ok(Bn128Field::from(0))
+        );
+
+        // the magnitude of a negative input is decimal unless prefixed with `0x`
+        assert_eq!(
+            parse_public_input::<Bn128Field>("-10"),
+            Ok(Bn128Field::from(-10))
+        );
+        assert_eq!(
+            parse_public_input::<Bn128Field>("-0x10"),
+            Ok(Bn128Field::from(-16))
+        );
+
+        let witness = interpreter
+            .execute(&program, &vec![Bn128Field::from(-10)])
+            .unwrap();
+        let mut proof = G16::generate_proof(program, witness, keypair.pk);
+        for (input, verified) in &[("-10", true), ("-0xa", true), ("-0x10", false)] {
+            proof.inputs[0] = input.to_string();
+            assert_eq!(
+                G16::verify_ref::<Bn128Field>(&vk, &proof),
+                Ok(*verified),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn proof_json() {
+        let (_, proof) = setup_and_prove(42);
+
+        let compact = proof.to_json_compact();
+        assert!(!compact.contains(char::is_whitespace));
+        assert!(compact.starts_with(r#"{"proof":{"a":["#));
+
+        let pretty = proof.to_json_pretty();
+        assert!(pretty.contains('\n'));
+        let reparsed: Proof<ProofPoints> = serde_json::from_str(&pretty).unwrap();
+        assert_eq!(reparsed, proof);
+        assert_eq!(reparsed.to_json_compact(), compact);
+    }
+
+    #[test]
+    fn setup_and_export_solidity() {
+        let (keypair, verifier) =
+            G16::setup_and_export_solidity(identity_program(), SolidityAbi::V2);
+
+        assert!(verifier.contains(&format!(
+            "vk.gamma_abc = new Pairing.G1Point[]({});",
+            keypair.vk.gamma_abc.len()
+        )));
+        assert_eq!(
+            verifier,
+            <G16 as ProofSystem<Bn128Field>>::export_solidity_verifier(keypair.vk, SolidityAbi::V2)
+        );
+    }
+
+    #[test]
+    fn extract_vk_from_pk() {
+        let keypair = G16::setup(identity_program());
+
+        assert_eq!(
+            G16::extract_vk_from_pk::<Bn128Field>(&keypair.pk),
+            Ok(keypair.vk)
+        );
+        assert!(
+            match G16::extract_vk_from_pk::<Bn128Field>(&keypair.pk[..100]) {
+                Err(Error::InvalidProvingKey(_)) => true,
+                _ => false,
+            }
+        );
+    }
+
+    #[test]
+    fn generate_proof_with_rng() {
+        let program = identity_program();
+
+        let keypair = G16::setup(program.clone());
+
+        let interpreter = Interpreter::default();
+
+        let witness = interpreter
+            .execute(&program, &vec![Bn128Field::from(42)])
+            .unwrap();
+
+        let prove = |seed: &[u32]| {
+            G16::generate_proof_with_rng(
+                program.clone(),
+                witness.clone(),
+                keypair.pk.clone(),
+                &mut ChaChaRng::from_seed(seed),
+                Some(G16::acknowledge_malleability()),
+                &mut io::sink(),
+            )
+        };
+
+        let proof = prove(&[1, 2, 3]);
+        let same_seed = prove(&[1, 2, 3]);
+        let other_seed = prove(&[4, 5, 6]);
+
+        assert_eq!(proof.raw, same_seed.raw);
+        assert_eq!(proof.proof, same_seed.proof);
+        assert_ne!(proof.raw, other_seed.raw);
+
+        for proof in vec![proof, same_seed, other_seed] {
+            assert!(<G16 as ProofSystem<Bn128Field>>::verify(
+                keypair.vk.clone(),
+                proof
+            ));
+        }
+    }
+
+    #[test]
+    fn verify_any() {
+        fn setup_and_prove<T: Field>() -> (VerificationKey, Proof<ProofPoints>) {
+            let program: Prog<T> = Prog {
+                main: Function {
+                    id: String::from("main"),
+                    arguments: vec![FlatVariable::new(0)],
+                    returns: vec![FlatVariable::public(0)],
+                    statements: vec![Statement::Constraint(
+                        FlatVariable::new(0).into(),
+                        FlatVariable::public(0).into(),
+                    )],
+                },
+                private: vec![false],
+            };
+
+            let keypair = <G16 as ProofSystem<T>>::setup(program.clone());
+
+            let interpreter = Interpreter::default();
+
+            let witness = interpreter.execute(&program, &vec![T::from(42)]).unwrap();
+
+            let proof = <G16 as ProofSystem<T>>::generate_proof(program, witness, keypair.pk);
+
+            (keypair.vk, proof)
+        }
+
+        let (bn128_vk, bn128_proof) = setup_and_prove::<Bn128Field>();
+        let (bls12_vk, bls12_proof) = setup_and_prove::<Bls12Field>();
+
+        assert_eq!(
+            G16::verify_any(
+                AnyVerificationKey::Bn128(bn128_vk.clone()),
+                AnyProof::Bn128(bn128_proof)
+            ),
+            Ok(true)
+        );
+        assert_eq!(
+            G16::verify_any(
+                AnyVerificationKey::Bls12(bls12_vk),
+                AnyProof::Bls12(bls12_proof.clone())
+            ),
+            Ok(true)
+        );
+        assert_eq!(
+            G16::verify_any(
+                AnyVerificationKey::Bn128(bn128_vk),
+                AnyProof::Bls12(bls12_proof)
+            ),
+            Err(Error::CurveMismatch {
+                vk: String::from("bn128"),
+                proof: String::from("bls12_381"),
+            })
+        );
+    }
+
+    #[test]
+    fn verifiable_bundle() {
+        let (keypair, proof) = setup_and_prove(42);
+
+        let bundle = VerifiableBundle::new::<Bn128Field>(keypair.vk, proof);
+        assert_eq!(bundle.scheme, "g16");
+        assert_eq!(bundle.curve, "bn128");
+
+        let reparsed = VerifiableBundle::from_json(&bundle.to_json()).unwrap();
+        assert_eq!(reparsed, bundle);
+        assert_eq!(reparsed.verify(), Ok(true));
+
+        let mut tampered = reparsed.clone();
+        tampered.proof.inputs[1] = format!("0x{:064x}", 43);
+        assert_eq!(tampered.verify(), Ok(false));
+
+        let mut other_scheme = reparsed.clone();
+        other_scheme.scheme = String::from("gm17");
+        assert_eq!(
+            other_scheme.verify(),
+            Err(Error::UnsupportedScheme(String::from("gm17")))
+        );
+
+        let mut unknown_curve = reparsed.clone();
+        unknown_curve.curve = String::from("secp256k1");
+        assert_eq!(
+            unknown_curve.verify(),
+            Err(Error::UnsupportedCurve(String::from("secp256k1")))
+        );
+
+        // the points of the key are not on the other curve
+        let mut other_curve = reparsed;
+        other_curve.curve = String::from("bls12_381");
+        match other_curve.verify() {
+            Err(Error::InvalidPoint { .. }) => {}
+            result => panic!("expected an invalid point, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn verify_without_public_inputs() {
+        let program: Prog<Bn128Field> = Prog {
+            main: Function {
+                id: String::from("main"),
+                arguments: vec![FlatVariable::new(0)],
+                returns: vec![],
+                statements: vec![Statement::Constraint(
+                    QuadComb::from_linear_combinations(
+                        FlatVariable::new(0).into(),
+                        FlatVariable::new(0).into(),
+                    ),
+                    FlatVariable::new(1).into(),
+                )],
+            },
+            private: vec![true],
+        };
+
+        let keypair = G16::setup(program.clone());
+        assert_eq!(keypair.vk.gamma_abc.len(), 1);
+
+        let interpreter = Interpreter::default();
+
+        let witness = interpreter
+            .execute(&program, &vec![Bn128Field::from(42)])
+            .unwrap();
+
+        let proof = G16::generate_proof(program, witness, keypair.pk);
+        assert!(proof.inputs.is_empty());
+
+        assert_eq!(
+            G16::verify_core::<Bn128Field>(&keypair.vk, &proof.proof, &[]),
+            Ok(true)
+        );
+        assert!(<G16 as ProofSystem<Bn128Field>>::verify(keypair.vk, proof));
+    }
+
+    #[test]
+    fn expected_proof_json_size() {
+        let (_, proof) = setup_and_prove(42);
+        assert_eq!(proof.inputs.len(), 2);
+
+        assert_eq!(
+            G16::expected_proof_json_size(2, "bn128"),
+            Ok(proof.to_json_pretty().len())
+        );
+
+        // each input adds a zero-padded hex string, its quotes, a comma and an indented line
+        assert_eq!(
+            G16::expected_proof_json_size(3, "bn128").unwrap()
+                - G16::expected_proof_json_size(2, "bn128").unwrap(),
+            2 + 64 + 2 + 1 + 1 + 4
+        );
+
+        assert!(
+            G16::expected_proof_json_size(2, "bls12_381").unwrap()
+                > G16::expected_proof_json_size(2, "bn128").unwrap()
+        );
+        assert_eq!(
+            G16::expected_proof_json_size(2, "secp256k1"),
+            Err(Error::UnsupportedCurve(String::from("secp256k1")))
+        );
+    }
+
+    #[test]
+    fn reject_infinity() {
+        let (keypair, proof) = setup_and_prove(42);
+
+        let infinity = G1Affine::new(format!("0x{:064x}", 0), format!("0x{:064x}", 0));
+        let infinity_error = |point: &str| Error::InvalidPoint {
+            point: point.to_string(),
+            reason: String::from("point at infinity"),
+        };
+
+        let mut with_infinity = proof.clone();
+        with_infinity.proof.a = infinity.clone();
+
+        assert_eq!(
+            with_infinity
+                .proof
+                .clone()
+                .into_bellman::<Bn128Field>()
+                .err(),
+            Some(infinity_error("a"))
+        );
+        assert_eq!(
+            G16::verify_core(
+                &keypair.vk,
+                &with_infinity.proof,
+                &[Bn128Field::from(42), Bn128Field::from(42)]
+            ),
+            Err(infinity_error("a"))
+        );
+        assert!(!<G16 as ProofSystem<Bn128Field>>::verify(
+            keypair.vk.clone(),
+            with_infinity
+        ));
+        assert_eq!(
+            ProofPoints::builder()
+                .a(infinity.clone())
+                .b(proof.proof.b.clone())
+                .c(proof.proof.c.clone())
+                .build::<Bn128Field>(),
+            Err(infinity_error("a"))
+        );
+
+        // `gamma_abc` points may be the point at infinity, the other points of the key may not
+        let mut vk = keypair.vk.clone();
+        vk.gamma_abc[1] = infinity.clone();
+        assert!(vk.to_bellman::<Bn128Field>().is_ok());
+
+        let mut vk = keypair.vk;
+        vk.alpha = infinity;
+        assert_eq!(
+            vk.to_bellman::<Bn128Field>().err(),
+            Some(infinity_error("alpha"))
+        );
+    }
+
+    #[test]
+    fn builders() {
+        let program = identity_program();
+
+        let keypair = G16::setup(program.clone());
+        let vk = keypair.vk.clone();
+
+        let built = VerificationKey::builder()
+            .alpha(vk.alpha.clone())
+            .beta(vk.beta.clone())
+            .gamma(vk.gamma.clone())
+            .delta(vk.delta.clone())
+            .gamma_abc(vk.gamma_abc.clone())
+            .build::<Bn128Field>()
+            .unwrap();
+
+        assert_eq!(built, keypair.vk);
+
+        assert_eq!(
+            VerificationKey::builder()
+                .alpha(vk.alpha.clone())
+                .beta(vk.beta.clone())
+                .gamma(vk.gamma.clone())
+                .gamma_abc(vk.gamma_abc.clone())
+                .build::<Bn128Field>(),
+            Err(Error::MissingPoint(String::from("delta")))
+        );
+
+        let off_curve = G1Affine::new(vk.alpha.0.clone(), vk.alpha.0.clone());
+        match VerificationKey::builder()
+            .alpha(vk.alpha.clone())
+            .beta(vk.beta.clone())
+            .gamma(vk.gamma.clone())
+            .delta(vk.delta.clone())
+            .gamma_abc(vec![vk.gamma_abc[0].clone(), off_curve.clone()])
+            .build::<Bn128Field>()
+        {
+            Err(Error::InvalidPoint { point, .. }) => assert_eq!(point, "gamma_abc[1]"),
+            _ => panic!("off curve point should be rejected"),
+        }
+
+        let witness = Interpreter::default()
+            .execute(&program, &vec![Bn128Field::from(42)])
+            .unwrap();
+        let proof = G16::generate_proof(program, witness, keypair.pk);
+
+        let points = ProofPoints::builder()
+            .a(proof.proof.a.clone())
+            .b(proof.proof.b.clone())
+            .c(proof.proof.c.clone())
+            .build::<Bn128Field>()
+            .unwrap();
+
+        assert_eq!(points, proof.proof);
+
+        assert!(ProofPoints::builder()
+            .a(off_curve)
+            .b(proof.proof.b.clone())
+            .c(proof.proof.c.clone())
+            .build::<Bn128Field>()
+            .is_err());
+    }
+
+    #[test]
+    fn public_input_range() {
+        fn check<T: Field>() {
+            let below = format!("0x{}", (T::modulus() - 1u32).to_str_radix(16));
+            let at = format!("0x{}", T::modulus().to_str_radix(16));
+            let above = format!("0x{}", (T::modulus() + 1u32).to_str_radix(16));
+
+            assert_eq!(parse_public_input::<T>(&below), Ok(T::max_value()));
+            assert!(parse_public_input::<T>(&at).is_err());
+            assert!(parse_public_input::<T>(&above).is_err());
+        }
+
+        check::<Bn128Field>();
+        check::<Bls12Field>();
+    }
+
+    #[test]
+    fn verify_out_of_range_input() {
+        let (keypair, mut proof) = setup_and_prove(42);
+
+        // 42 + p reduces to 42, but is out of range
+        proof.inputs[0] = format!("0x{}", (Bn128Field::modulus() + 42u32).to_str_radix(16));
+
+        assert!(!<G16 as ProofSystem<Bn128Field>>::verify(keypair.vk, proof));
+    }
+
+    #[test]
+    fn setup_with_report() {
+        let program = identity_program();
+
+        let (keypair, report) = G16::setup_with_report(program.clone());
+
+        assert_eq!(report.constraint_count, 1);
+        assert_eq!(report.pk_size, keypair.pk.len());
+        assert_eq!(report.gamma_abc_len, keypair.vk.gamma_abc.len());
+        assert_eq!(report.gamma_abc_len, 3);
+        assert_eq!(report.warnings.len(), 1);
+        assert!(report.warnings[0].contains("malleability"));
+
+        // the setup is the same as the one of `ProofSystem::setup`
+        assert_eq!(keypair.vk, G16::setup(program).vk);
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["gamma_abc_len"], 3);
+    }
+
+    #[test]
+    fn setup_with_progress() {
+        let program = identity_program();
+
+        let mut reports = vec![];
+        let keypair = G16::setup_with_progress(
+            program.clone(),
+            |stage, done, total| reports.push((stage, done, total)),
+            Some(G16::acknowledge_malleability()),
+            &mut io::sink(),
+        );
+
+        assert_eq!(
+            reports,
+            vec![
+                (SetupStage::CountingConstraints, 0, 3),
+                (SetupStage::GeneratingParameters, 1, 3),
+                (SetupStage::WritingKey, 2, 3),
+                (SetupStage::WritingKey, 3, 3),
+            ]
+        );
+        assert!(reports.windows(2).all(|w| w[0].1 <= w[1].1));
+
+        assert_eq!(keypair.vk, G16::setup(program).vk);
+    }
+
+    #[test]
+    fn timings() {
+        let program = identity_program();
+
+        let warning = format!("{}\n", G16_WARNING).into_bytes();
+
+        let mut out = vec![];
+        let (keypair, setup_timings) = G16::setup_timed(program.clone(), None, &mut out);
+        assert_eq!(out, warning);
+        assert!(setup_timings.setup_us.unwrap() > 0);
+        assert_eq!(setup_timings.read_key_us, None);
+        assert_eq!(setup_timings.prove_us, None);
+
+        let witness = Interpreter::default()
+            .execute(&program, &vec![Bn128Field::from(42)])
+            .unwrap();
+
+        let acknowledged = G16::acknowledge_malleability();
+        let mut out = vec![];
+        let (proof, timings) =
+            G16::generate_proof_timed(program, witness, keypair.pk, Some(acknowledged), &mut out);
+        assert!(out.is_empty());
+        assert_eq!(timings.setup_us, None);
+        assert!(timings.read_key_us.unwrap() > 0);
+        assert!(timings.prove_us.unwrap() > 0);
+
+        assert!(<G16 as ProofSystem<Bn128Field>>::verify(keypair.vk, proof));
+    }
+
+    #[test]
+    fn keccak256_commitment() {
+        let keypair = G16::setup(identity_program());
+        let vk = keypair.vk;
+
+        // alpha (2) + beta, gamma, delta (4 each) + gamma_abc (3 points of 2)
+        assert_eq!(vk.to_eth_words().len(), 20);
+        assert_eq!(vk.keccak256_commitment(), vk.clone().keccak256_commitment());
+
+        let mut modified = vk.clone();
+        modified.gamma_abc.swap(0, 1);
+        assert_ne!(vk.keccak256_commitment(), modified.keccak256_commitment());
+    }
+
+    #[test]
+    fn onchain_costs() {
+        let keypair = G16::setup(identity_program());
+        let vk = keypair.vk;
+
+        assert_eq!(vk.gamma_abc.len(), 3);
+        assert_eq!(vk.onchain_scalar_mul_count(), 2);
+        assert_eq!(
+            vk.onchain_gas_estimate(),
+            2 * (6_000 + 150) + 150 + 45_000 + 4 * 34_000
+        );
+
+        assert_eq!(VerificationKey::marginal_input_gas(), 6_000 + 150);
+
+        let mut one_more = vk.clone();
+        one_more.gamma_abc.push(vk.gamma_abc[0].clone());
+        assert_eq!(
+            one_more.onchain_gas_estimate() - vk.onchain_gas_estimate(),
+            VerificationKey::marginal_input_gas()
+        );
+    }
+
+    #[test]
+    fn from_eth_words() {
+        let keypair = G16::setup(identity_program());
+        let vk = keypair.vk;
+
+        let words = vk.to_eth_words();
+        assert_eq!(VerificationKey::from_eth_words(&words), Ok(vk));
+
+        assert_eq!(
+            VerificationKey::from_eth_words(&words[..words.len() - 1]),
+            Err(Error::InvalidWordCount(words.len() - 1))
+        );
+        assert_eq!(
+            VerificationKey::from_eth_words(&words[..14]),
+            Err(Error::InvalidWordCount(14))
+        );
+    }
+
+    #[test]
+    fn eth_word() {
+        let mut expected = [0u8; 32];
+        expected[30] = 0x01;
+        expected[31] = 0x2a;
+        assert_eq!(to_eth_word("0x012a"), expected);
+    }
+
+    #[test]
+    fn verify_on_curve_by_name() {
+        use zokrates_field::{visit_field, FieldVisitor};
+
+        struct Verify(VerificationKey, Proof<ProofPoints>);
+
+        impl FieldVisitor for Verify {
+            type Output = bool;
+
+            fn visit<T: Field>(self) -> bool {
+                <G16 as ProofSystem<T>>::verify(self.0, self.1)
+            }
+        }
+
+        let (keypair, proof) = setup_and_prove(42);
+
+        assert_eq!(
+            visit_field(
+                Bn128Field::name(),
+                Verify(keypair.vk.clone(), proof.clone())
+            ),
+            Some(true)
+        );
+        assert_eq!(visit_field("bw6_761", Verify(keypair.vk, proof)), None);
+    }
+
+    #[test]
+    fn validate_vk() {
+        let keypair = G16::setup(identity_program());
+        assert_eq!(keypair.vk.validate::<Bn128Field>(), Ok(()));
+
+        // (1, 3) is not on the curve y^2 = x^3 + 3
+        let mut off_curve = keypair.vk.clone();
+        off_curve.gamma_abc[1] = G1Affine::new(format!("0x{:064x}", 1), format!("0x{:064x}", 3));
+        assert_eq!(
+            off_curve.validate::<Bn128Field>(),
+            Err(Error::InvalidPoint {
+                point: String::from("gamma_abc[1]"),
+                reason: String::from("coordinate(s) do not lie on the curve"),
+            })
+        );
+
+        let mut infinity = keypair.vk;
+        let zero = G1Affine::new(format!("0x{:064x}", 0), format!("0x{:064x}", 0));
+        infinity.delta = G2Affine::new(zero.clone(), zero);
+        assert_eq!(
+            infinity.validate::<Bn128Field>(),
+            Err(Error::InvalidPoint {
+                point: String::from("delta"),
+                reason: String::from("point at infinity"),
+            })
+        );
+    }
+
+    #[test]
+    fn acknowledge_malleability() {
+        let program = identity_program();
+
+        let witness = Interpreter::default()
+            .execute(&program, &vec![Bn128Field::from(42)])
+            .unwrap();
+
+        let warning = format!("{}\n", G16_WARNING).into_bytes();
+
+        let mut out = vec![];
+        let keypair = G16::setup_writing_warning(program.clone(), None, &mut out);
+        G16::generate_proof_writing_warning(
+            program.clone(),
+            witness.clone(),
+            keypair.pk.clone(),
+            None,
+            &mut out,
+        );
+        assert_eq!(out, [warning.clone(), warning].concat());
+
+        let acknowledged = G16::acknowledge_malleability();
+        let mut out = vec![];
+        let keypair = G16::setup_writing_warning(program.clone(), Some(acknowledged), &mut out);
+        let proof = G16::generate_proof_writing_warning(
+            program,
+            witness,
+            keypair.pk,
+            Some(acknowledged),
+            &mut out,
+        );
+        assert!(out.is_empty());
+        assert!(<G16 as ProofSystem<Bn128Field>>::verify(keypair.vk, proof));
+    }
+
+    #[test]
+    fn setup_with_compression() {
+        let program = identity_program();
+
+        let mut out = vec![];
+        let uncompressed = G16::setup_with_compression(
+            program.clone(),
+            KeyCompression::Uncompressed,
+            None,
+            &mut out,
+        );
+        assert_eq!(out, format!("{}\n", G16_WARNING).into_bytes());
+
+        let compressed = G16::setup_with_compression(
+            program.clone(),
+            KeyCompression::Compressed,
+            Some(G16::acknowledge_malleability()),
+            &mut io::sink(),
+        );
+
+        assert!(compressed.pk.starts_with(COMPRESSED_PK_HEADER));
+        assert!(!uncompressed.pk.starts_with(COMPRESSED_PK_HEADER));
+        assert!(compressed.pk.len() < uncompressed.pk.len());
+
+        // both encodings hold the same parameters
+        assert!(
+            read_proving_key::<Bn128Field>(&compressed.pk).unwrap()
+                == read_proving_key::<Bn128Field>(&uncompressed.pk).unwrap()
+        );
+        assert_eq!(
+            G16::extract_vk_from_pk::<Bn128Field>(&compressed.pk).unwrap(),
+            compressed.vk
+        );
+
+        let witness = Interpreter::default()
+            .execute(&program, &vec![Bn128Field::from(42)])
+            .unwrap();
+        let proof = <G16 as ProofSystem<Bn128Field>>::generate_proof(
+            program,
+            witness,
+            compressed.pk.clone(),
+        );
+
+        assert!(<G16 as ProofSystem<Bn128Field>>::verify(
+            compressed.vk,
+            proof
+        ));
+
+        let mut truncated = compressed.pk;
+        truncated.pop();
+        assert!(read_proving_key::<Bn128Field>(&truncated).is_err());
+    }
+
+    #[test]
+    fn setup_vk_only() {
+        let program = identity_program();
+
+        let acknowledged = G16::acknowledge_malleability();
+        let mut out = vec![];
+        let vk = G16::setup_vk_only(program.clone(), Some(acknowledged), &mut out);
+
+        assert!(out.is_empty());
+        assert_eq!(vk, G16::setup_acknowledged(program, acknowledged).vk);
+    }
+
+    #[test]
+    fn setup_malformed_program() {
+        use std::panic::catch_unwind;
+
+        let mut malformed = identity_program();
+        malformed.private.pop();
+
+        // every setup validates the program before running bellman
+        let setups: Vec<fn(ir::Prog<Bn128Field>)> = vec![
+            |p| drop(<G16 as ProofSystem<Bn128Field>>::setup(p)),
+            |p| drop(G16::setup_vk_only(p, None, &mut io::sink())),
+            |p| {
+                drop(G16::setup_with_compression(
+                    p,
+                    KeyCompression::Compressed,
+                    None,
+                    &mut io::sink(),
+                ))
+            },
+            |p| drop(G16::setup_with_report(p)),
+            |p| {
+                drop(G16::setup_with_progress(
+                    p,
+                    |_, _, _| {},
+                    None,
+                    &mut io::sink(),
+                ))
+            },
+            |p| drop(G16::setup_timed(p, None, &mut io::sink())),
+        ];
+        for setup in setups {
+            assert!(catch_unwind(|| setup(malformed.clone())).is_err());
+        }
+    }
+
+    #[test]
+    fn witness_to_public_inputs() {
+        let program = identity_program();
+
+        let keypair = G16::setup(program.clone());
+
+        let interpreter = Interpreter::default();
+
+        let witness = interpreter
+            .execute(&program, &vec![Bn128Field::from(42)])
+            .unwrap();
+
+        let inputs = witness.to_public_inputs(&program).unwrap();
+        let proof = G16::generate_proof(program, witness, keypair.pk);
+
+        assert_eq!(inputs, proof.inputs);
+    }
+
+    #[test]
+    fn deserialize_with_extra_fields() {
+        let (keypair, proof) = setup_and_prove(42);
+
+        // fields added by other tools are ignored, as none of the types denies unknown fields
+        let mut proof_json = serde_json::to_value(&proof).unwrap();
+        proof_json["created_at"] = serde_json::json!("2020-07-01T00:00:00Z");
+        proof_json["proof"]["circuit_id"] = serde_json::json!(1);
+        let mut vk_json = serde_json::to_value(&keypair.vk).unwrap();
+        vk_json["created_at"] = serde_json::json!("2020-07-01T00:00:00Z");
+
+        let parsed_proof: Proof<ProofPoints> =
+            serde_json::from_str(&proof_json.to_string()).unwrap();
+        let parsed_vk: VerificationKey = serde_json::from_str(&vk_json.to_string()).unwrap();
+
+        assert_eq!(parsed_proof, proof);
+        assert_eq!(parsed_vk, keypair.vk);
+        assert!(<G16 as ProofSystem<Bn128Field>>::verify(
+            parsed_vk,
+            parsed_proof
+        ));
+    }
+
+    #[test]
+    fn describe_equation() {
+        // the argument is public, so that the program has two public inputs
+        let vk = G16::setup(identity_program()).vk;
+
+        let description = vk.describe_equation();
+        assert_eq!(
+            description,
+            "e(A, B) = e(alpha, beta) * e(vk_x, gamma) * e(C, delta)\n\
+             where vk_x = gamma_abc[0] + input[0] * gamma_abc[1] + input[1] * gamma_abc[2]\n\
+             for 2 public inputs input[i], each smaller than the scalar field modulus r"
+        );
+        assert_eq!(
+            description.matches("gamma_abc[").count(),
+            vk.gamma_abc.len()
+        );
+
+        let mut no_inputs = vk;
+        no_inputs.gamma_abc.truncate(1);
+        assert!(no_inputs
+            .describe_equation()
+            .contains("where vk_x = gamma_abc[0]\nfor 0 public inputs"));
+    }
+
+    #[test]
+    fn compute_vk_x() {
+        let vk = <G16 as ProofSystem<Bn128Field>>::setup(identity_program()).vk;
+
+        assert_eq!(
+            vk.compute_vk_x(&[Bn128Field::from(0), Bn128Field::from(0)]),
+            vk.gamma_abc[0]
+        );
+
+        // gamma_abc[0] + 2 * gamma_abc[1] + gamma_abc[2], by repeated addition
+        let g: Vec<_> = vk
+            .gamma_abc
+            .iter()
+            .map(|g1| serialization::to_g1::<Bn128Field>(g1.clone()).unwrap())
+            .collect();
+        let mut expected = g[0].into_projective();
+        expected.add_assign_mixed(&g[1]);
+        expected.add_assign_mixed(&g[1]);
+        expected.add_assign_mixed(&g[2]);
+
+        assert_eq!(
+            vk.compute_vk_x(&[Bn128Field::from(2), Bn128Field::from(1)]),
+            parse_g1::<Bn128Field>(&expected.into_affine())
+        );
+    }
+
+    #[test]
+    fn same_interface() {
+        // `~out_0 == multiplier * _0`
+        let program = |private, multiplier: u32| -> Prog<Bn128Field> {
+            Prog {
+                main: Function {
+                    id: String::from("main"),
+                    arguments: vec![FlatVariable::new(0)],
+                    returns: vec![FlatVariable::public(0)],
+                    statements: vec![Statement::Constraint(
+                        LinComb::summand(multiplier, FlatVariable::new(0)).into(),
+                        FlatVariable::public(0).into(),
+                    )],
+                },
+                private: vec![private],
+            }
+        };
+
+        let vk = <G16 as ProofSystem<Bn128Field>>::setup(program(false, 1)).vk;
+        let other_program = <G16 as ProofSystem<Bn128Field>>::setup(program(false, 2)).vk;
+        let private_argument = <G16 as ProofSystem<Bn128Field>>::setup(program(true, 1)).vk;
+
+        // the keys of different programs differ, even with the same interface
+        assert_ne!(vk, other_program);
+        assert!(vk.same_interface(&other_program));
+        assert!(!vk.same_interface(&private_argument));
+        assert!(!private_argument.same_interface(&vk));
+    }
+
+    #[test]
+    fn write_vk_json() {
+        let keypair = <G16 as ProofSystem<Bn128Field>>::setup(identity_program());
+
+        let mut cursor = std::io::Cursor::new(vec![]);
+        keypair.vk.write_json(&mut cursor).unwrap();
+
+        let vk: VerificationKey = serde_json::from_slice(cursor.get_ref()).unwrap();
+        assert_eq!(vk, keypair.vk);
+        assert_eq!(
+            cursor.into_inner(),
+            serde_json::to_vec(&keypair.vk).unwrap()
+        );
+    }
+
+    #[test]
+    fn expected_public_input_count() {
+        let keypair = G16::setup(identity_program());
+
+        assert_eq!(
+            keypair.vk.expected_public_input_count(),
+            keypair.vk.gamma_abc.len() - 1
+        );
+        // the argument and the return value
+        assert_eq!(keypair.vk.expected_public_input_count(), 2);
+    }
+
+    #[test]
+    fn vk_to_bellman() {
+        let (keypair, proof) = setup_and_prove(42);
+
+        let vk = keypair.vk.to_bellman::<Bn128Field>().unwrap();
+        let pvk = bellman::groth16::prepare_verifying_key(&vk);
+        let inputs: Vec<_> = vec![42, 42]
+            .into_iter()
+            .map(|i| Bn128Field::from(i).into_bellman())
+            .collect();
+
+        let bellman_proof = proof.proof.to_bellman::<Bn128Field>().unwrap();
+        assert!(bellman::groth16::verify_proof(&pvk, &bellman_proof, &inputs).unwrap());
+        assert!(
+            !bellman::groth16::verify_proof(&pvk, &bellman_proof, &inputs[..1]).unwrap_or(false)
+        );
+    }
+
+    #[test]
+    fn g2_order() {
+        let g2 = <<Bn128Field as Field>::BellmanEngine as Engine>::G2Affine::one();
+        let reversed = parse_g2::<Bn128Field>(&g2);
+        let natural = reversed.in_math_order();
+
+        assert_eq!(
+            serialization::to_g2::<Bn128Field>(reversed.clone(), G2Order::BellmanReversed),
+            Ok(g2)
+        );
+        assert_eq!(
+            serialization::to_g2::<Bn128Field>(natural.clone(), G2Order::Natural),
+            Ok(g2)
+        );
+        assert_ne!(
+            serialization::to_g2::<Bn128Field>(natural, G2Order::BellmanReversed),
+            Ok(g2)
+        );
+
+        let (keypair, proof) = setup_and_prove(42);
+
+        let natural_proof = ProofPoints {
+            b: proof.proof.b.in_math_order(),
+            ..proof.proof.clone()
+        };
+        assert_eq!(
+            natural_proof.to_bellman_with_order::<Bn128Field>(G2Order::Natural),
+            proof.proof.to_bellman::<Bn128Field>()
+        );
+
+        let mut natural_vk = keypair.vk.clone();
+        natural_vk.beta = natural_vk.beta.in_math_order();
+        natural_vk.gamma = natural_vk.gamma.in_math_order();
+        natural_vk.delta = natural_vk.delta.in_math_order();
+        let vk = natural_vk
+            .to_bellman_with_order::<Bn128Field>(G2Order::Natural)
+            .unwrap();
+        assert!(vk == keypair.vk.to_bellman::<Bn128Field>().unwrap());
+    }
+
+    #[test]
+    fn vk_from_bellman_bytes() {
+        let program = identity_program();
+
+        let parameters = Computation::without_witness(program.clone()).setup();
+        let mut bytes = vec![];
+        parameters.vk.write(&mut bytes).unwrap();
+
+        let vk = VerificationKey::from_bellman_bytes::<Bn128Field>(&bytes).unwrap();
+        assert_eq!(
+            vk,
+            VerificationKey::from_bellman::<Bn128Field>(&parameters.vk)
+        );
+
+        let mut pk = vec![];
+        parameters.write(&mut pk).unwrap();
+        let witness = Interpreter::default()
+            .execute(&program, &vec![Bn128Field::from(42)])
+            .unwrap();
+        let proof = G16::generate_proof(program, witness, pk);
+        assert_eq!(G16::verify_ref::<Bn128Field>(&vk, &proof), Ok(true));
+
+        bytes.push(0);
+        assert_eq!(
+            VerificationKey::from_bellman_bytes::<Bn128Field>(&bytes),
+            Err(Error::InvalidBellmanKey(String::from("1 trailing bytes")))
+        );
+        assert!(VerificationKey::from_bellman_bytes::<Bn128Field>(&bytes[..10]).is_err());
+    }
+
+    #[test]
+    fn random_satisfying_inputs() {
+        let program = identity_program();
+
+        let keypair = G16::setup(program.clone());
+
+        let interpreter = Interpreter::default();
+        let mut rng = rand::thread_rng();
+
+        let inputs = interpreter
+            .random_satisfying_inputs(&program, &mut rng)
+            .unwrap();
+        assert_eq!(inputs.len(), 1);
+
+        let witness = interpreter.execute(&program, &inputs).unwrap();
+        let proof = G16::generate_proof(program.clone(), witness, keypair.pk);
+        assert!(<G16 as ProofSystem<Bn128Field>>::verify(keypair.vk, proof));
+
+        // only 42 satisfies `_0 == 42`
+        let mut over_constrained = program;
+        over_constrained.main.statements.push(Statement::Constraint(
+            FlatVariable::new(0).into(),
+            (LinComb::one() * &Bn128Field::from(42)).into(),
+        ));
+        assert_eq!(
+            interpreter.random_satisfying_inputs(&over_constrained, &mut rng),
+            None
+        );
+    }
+
+    #[test]
+    fn producer_version() {
+        let (keypair, proof) = setup_and_prove(42);
+
+        assert_eq!(keypair.vk.producer_version(), Some(ZOKRATES_VERSION));
+        assert_eq!(proof.producer_version.as_deref(), Some(ZOKRATES_VERSION));
+
+        let strict = VerifyOptions {
+            strict_version: true,
+            ..VerifyOptions::default()
+        };
+        let with_version = |version: Option<&str>| Proof {
+            producer_version: version.map(String::from),
+            ..proof.clone()
+        };
+        let vk = &keypair.vk;
+        let verify = |proof: Proof<ProofPoints>, options: &VerifyOptions| {
+            G16::verify_with_options::<Bn128Field>(vk.clone(), proof, options)
+        };
+
+        // same version
+        assert!(verify(proof.clone(), &strict));
+        // older version, or none
+        assert!(verify(with_version(Some("0.0.1")), &strict));
+        assert!(verify(with_version(None), &strict));
+
+        // newer major version
+        let newer = with_version(Some("999.0.0"));
+        assert!(!verify(newer.clone(), &strict));
+        assert!(verify(newer.clone(), &VerifyOptions::default()));
+        assert!(<G16 as ProofSystem<Bn128Field>>::verify(
+            keypair.vk.clone(),
+            newer.clone()
+        ));
+        match G16::check_producer_versions(&keypair.vk, &newer) {
+            Err(Error::IncompatibleVersion(reason)) => assert!(reason.contains("999.0.0")),
+            r => panic!("unexpected result {:?}", r),
+        }
+
+        // the version survives serialization, and is optional when reading
+        let json: serde_json::Value = serde_json::from_str(&proof.to_json_pretty()).unwrap();
+        assert_eq!(json["producer_version"], ZOKRATES_VERSION);
+        let mut json = serde_json::to_value(&keypair.vk).unwrap();
+        json.as_object_mut().unwrap().remove("producer_version");
+        let vk: VerificationKey = serde_json::from_value(json).unwrap();
+        assert_eq!(vk.producer_version(), None);
+
+        // keys which were not generated by a setup of ZoKrates have none
+        let bellman_vk = keypair.vk.to_bellman::<Bn128Field>().unwrap();
+        let mut bytes = vec![];
+        bellman_vk.write(&mut bytes).unwrap();
+        let vk = VerificationKey::from_bellman_bytes::<Bn128Field>(&bytes).unwrap();
+        assert_eq!(vk.producer_version(), None);
+    }
+
+    #[test]
+    fn bench_verify_once() {
+        // about twenty times the latency of an unoptimized build, so that a slow machine passes
+        // but a regression by an order of magnitude does not
+        const LATENCY_BUDGET_US: u128 = 2_000_000;
+
+        let (keypair, proof) = setup_and_prove(42);
+
+        // the fastest of a few runs, which is the least affected by the load of the machine
+        let mut fastest = u128::max_value();
+        for _ in 0..3 {
+            let (verified, latency) =
+                G16::bench_verify_once::<Bn128Field>(keypair.vk.clone(), proof.clone());
+            assert!(verified);
+            fastest = std::cmp::min(fastest, latency);
+        }
+        assert!(
+            fastest < LATENCY_BUDGET_US,
+            "verification took {}us, the budget is {}us",
+            fastest,
+            LATENCY_BUDGET_US
+        );
+
+        let mut tampered = proof;
+        tampered.inputs[1] = format!("0x{:0>64}", "2b");
+        let (verified, _) = G16::bench_verify_once::<Bn128Field>(keypair.vk, tampered);
+        assert!(!verified);
+    }
+
+    #[test]
+    fn verify_timed() {
+        let (keypair, proof) = setup_and_prove(42);
+
+        let (verified, timings) =
+            G16::verify_timed::<Bn128Field>(keypair.vk.clone(), proof.clone());
+        assert_eq!(
+            verified,
+            <G16 as ProofSystem<Bn128Field>>::verify(keypair.vk.clone(), proof.clone())
+        );
+        assert!(verified);
+        assert!(timings.parse_us > 0);
+        assert!(timings.prepare_us > 0);
+        assert!(timings.pairing_us > 0);
+
+        let mut wrong_inputs = proof;
+        wrong_inputs.inputs[0] = format!("0x{:064x}", 43);
+        let (verified, _) =
+            G16::verify_timed::<Bn128Field>(keypair.vk.clone(), wrong_inputs.clone());
+        assert_eq!(
+            verified,
+            <G16 as ProofSystem<Bn128Field>>::verify(keypair.vk, wrong_inputs)
+        );
+        assert!(!verified);
+    }
+
+    #[test]
+    fn provenance_digest() {
+        let program = |factor: u32| -> Prog<Bn128Field> {
+            Prog {
+                main: Function {
+                    id: String::from("main"),
+                    arguments: vec![FlatVariable::new(0)],
+                    returns: vec![FlatVariable::public(0)],
+                    statements: vec![Statement::Constraint(
+                        QuadComb::from_linear_combinations(
+                            LinComb::summand(factor, FlatVariable::new(0)),
+                            LinComb::one(),
+                        ),
+                        FlatVariable::public(0).into(),
+                    )],
+                },
+                private: vec![false],
+            }
+        };
+
+        let keypair = G16::setup(program(1));
+        let digest = keypair.provenance_digest(&program(1));
+
+        // stable for the same inputs
+        assert_eq!(keypair.provenance_digest(&program(1)), digest);
+        assert_eq!(
+            G16::setup(program(1)).provenance_digest(&program(1)),
+            digest
+        );
+
+        // the program changes
+        assert_ne!(keypair.provenance_digest(&program(2)), digest);
+
+        // the proving key changes
+        let mut other = G16::setup(program(1));
+        let last = other.pk.len() - 1;
+        other.pk[last] ^= 1;
+        assert_ne!(other.provenance_digest(&program(1)), digest);
+
+        // the verification key changes
+        let mut other = G16::setup(program(1));
+        other.vk.alpha = other.vk.gamma_abc[0].clone();
+        assert_ne!(other.provenance_digest(&program(1)), digest);
+    }
+}
@@ -0,0 +1,1842 @@
+use bellman::groth16::{Proof as BellmanProof, VerifyingKey};
+use pairing::ff::{Field as PairingField, PrimeField};
+use pairing::{CurveAffine, CurveProjective, EncodedPoint, Engine};
+use sha3::{Digest, Keccak256};
+use std::collections::HashMap;
+use std::io::Read;
+
+use zokrates_field::{Bn128Field, Field};
+
+use super::{
+    decode_finite_g1, decode_finite_g2, decode_g1, in_subgroup, parse_public_input, serialization,
+    serialize_raw, Error, G2Order, ProofPoints, VerifiableBundle, VerificationKey, G16, G16_SCHEME,
+};
+use crate::flat_absy::FlatVariable;
+use crate::ir;
+use proof_system::{pack_public_inputs, G1Affine, G2Affine, Proof, ZOKRATES_VERSION};
+
+impl Proof<ProofPoints> {
+    /// Checks that the proof has as many public inputs as `vk` expects, and that each of them
+    /// decodes to an element of `T`
+    pub fn validate_against_vk<T: Field>(&self, vk: &VerificationKey) -> Result<(), Error> {
+        let expected = vk.expected_public_input_count();
+        if self.inputs.len() != expected {
+            return Err(Error::InputCountMismatch {
+                expected,
+                found: self.inputs.len(),
+            });
+        }
+
+        for input in &self.inputs {
+            parse_public_input::<T>(input)?;
+        }
+
+        Ok(())
+    }
+
+    /// Regenerates `raw` from the current proof points, so that both representations of the
+    /// proof agree after the points were modified. Fails if the points are not valid points of `T`.
+    pub fn resync_raw<T: Field>(&mut self) -> Result<(), Error> {
+        let proof = self.proof.to_bellman::<T>()?;
+        self.raw = serialize_raw::<T>(&proof);
+        Ok(())
+    }
+
+    /// Checks that `raw` decodes to the same points as the proof points, which verification reads
+    /// while other tools may read `raw`. An empty `raw`, as left by `without_raw`, is consistent.
+    pub fn check_raw_consistency<T: Field>(&self) -> Result<(), Error> {
+        if self.raw.is_empty() {
+            return Ok(());
+        }
+
+        let bytes = hex::decode(&self.raw).map_err(|e| Error::InvalidRaw(e.to_string()))?;
+        let raw = BellmanProof::<T::BellmanEngine>::read(&bytes[..])
+            .map_err(|e| Error::InvalidRaw(e.to_string()))?;
+        let points = self.proof.to_bellman::<T>()?;
+
+        let mismatch = |point: &str| {
+            Err(Error::RawMismatch {
+                point: point.to_string(),
+            })
+        };
+
+        if raw.a != points.a {
+            return mismatch("a");
+        }
+        if raw.b != points.b {
+            return mismatch("b");
+        }
+        if raw.c != points.c {
+            return mismatch("c");
+        }
+
+        // trailing bytes are not part of the proof
+        if serialize_raw::<T>(&raw).len() != self.raw.len() {
+            return Err(Error::InvalidRaw(String::from("unexpected trailing bytes")));
+        }
+
+        Ok(())
+    }
+
+    /// Drops `raw`, which duplicates the points in bellman's serialization, to shrink the serialized
+    /// proof. G16 verification only reads the points, and `resync_raw` restores `raw`.
+    pub fn without_raw(self) -> Self {
+        Proof {
+            raw: String::new(),
+            ..self
+        }
+    }
+
+    /// A keccak256 hash of the public inputs as 32 byte big endian words, which identifies them
+    /// independently of their encoding. The inputs are read like when verifying the proof, see
+    /// `parse_field_input`, so that the hash commits to the verified values. Fails if an input is
+    /// not an element of `T`.
+    pub fn public_input_hash<T: Field>(&self) -> Result<[u8; 32], Error> {
+        let mut hasher = Keccak256::new();
+        for input in &self.inputs {
+            let value = parse_public_input::<T>(input)?.to_biguint();
+
+            let bytes = value.to_bytes_be();
+            let mut word = [0u8; 32];
+            word[32 - bytes.len()..].copy_from_slice(&bytes);
+            hasher.input(&word[..]);
+        }
+
+        let mut hash = [0u8; 32];
+        hash.copy_from_slice(hasher.result().as_slice());
+        Ok(hash)
+    }
+}
+
+/// Options controlling how `G16::verify_with_options` treats its inputs
+#[derive(Debug, Default, Clone)]
+pub struct VerifyOptions {
+    /// Reject keys, proofs and public inputs which are not encoded canonically, i.e. as the `0x`-prefixed,
+    /// lowercase, zero-padded hex representation of a fully reduced field element. Points are
+    /// normalized when they are deserialized, so only the range of their coordinates is checked
+    /// for keys and proofs read from JSON.
+    pub strict_canonical: bool,
+    /// Reject proofs whose `raw` encoding does not match their points, see
+    /// `Proof::check_raw_consistency`
+    pub check_raw: bool,
+    /// Reject keys and proofs generated by an incompatible version of ZoKrates, which are otherwise
+    /// accepted, see `G16::check_producer_versions`
+    pub strict_version: bool,
+}
+
+/// The result of `G16::verify_verbose`
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerifyOutcome<T> {
+    pub verified: bool,
+    /// the public inputs fed into the verification. If an input cannot be decoded, the verification
+    /// fails and only the inputs preceding it are included
+    pub inputs: Vec<T>,
+    /// why the key or the proof was generated by an incompatible version of ZoKrates, if it was,
+    /// see `G16::check_producer_versions`. It does not fail the verification.
+    pub incompatible_version: Option<Error>,
+}
+
+/// A term `e(p, q)` of the pairing product checked by the verification
+#[derive(Debug, Clone, PartialEq)]
+pub struct PairingTerm {
+    /// the term as written in the pairing product, e.g. `e(-c, delta)`
+    pub name: &'static str,
+    /// the first invalid point of the term, if any. All points must be in the prime order subgroups,
+    /// and only `vk_x` may be the point at infinity
+    pub error: Option<Error>,
+}
+
+impl PairingTerm {
+    pub fn is_well_formed(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// The result of `G16::verify_diagnostic`
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerificationDiagnostic {
+    /// the terms `e(a, b)`, `e(-vk_x, gamma)`, `e(-c, delta)` and `e(-alpha, beta)`, in this order
+    pub terms: Vec<PairingTerm>,
+    /// the product of the terms after the final exponentiation, encoded with `Field::fqk_to_bytes`,
+    /// or `None` if a term is not well-formed
+    pub product: Option<Vec<u8>>,
+    /// whether the product is the identity, i.e. whether the proof verifies
+    pub verified: bool,
+}
+
+impl G16 {
+    pub fn verify_with_options<T: Field>(
+        vk: VerificationKey,
+        proof: Proof<ProofPoints>,
+        options: &VerifyOptions,
+    ) -> bool {
+        if options.strict_version && G16::check_producer_versions(&vk, &proof).is_err() {
+            return false;
+        }
+
+        if options.strict_canonical
+            && !(vk.is_canonical::<T>()
+                && proof.proof.is_canonical::<T>()
+                && proof
+                    .inputs
+                    .iter()
+                    .all(|i| serialization::is_canonical_fr::<T>(i)))
+        {
+            return false;
+        }
+
+        if options.check_raw && proof.check_raw_consistency::<T>().is_err() {
+            return false;
+        }
+
+        if proof.validate_against_vk::<T>(&vk).is_err() {
+            return false;
+        }
+
+        G16::verify_streaming(
+            vk,
+            proof.proof,
+            proof.inputs.iter().map(|s| parse_public_input::<T>(s)),
+        )
+        .unwrap_or(false)
+    }
+
+    /// Verifies a proof like `ProofSystem::verify`, also returning the public inputs as decoded
+    /// from `proof.inputs`
+    pub fn verify_verbose<T: Field>(
+        vk: VerificationKey,
+        proof: Proof<ProofPoints>,
+    ) -> VerifyOutcome<T> {
+        let incompatible_version = G16::check_producer_versions(&vk, &proof).err();
+
+        let mut inputs = vec![];
+        for input in &proof.inputs {
+            match parse_public_input::<T>(input) {
+                Ok(input) => inputs.push(input),
+                Err(_) => {
+                    return VerifyOutcome {
+                        verified: false,
+                        inputs,
+                        incompatible_version,
+                    }
+                }
+            }
+        }
+
+        let verified =
+            G16::verify_streaming(vk, proof.proof, inputs.iter().cloned().map(Ok::<_, ()>))
+                .unwrap();
+
+        VerifyOutcome {
+            verified,
+            inputs,
+            incompatible_version,
+        }
+    }
+
+    /// Verifies a proof against public inputs which are consumed one at a time, so that they never
+    /// need to be held in memory together. The first error returned by `inputs` aborts the
+    /// verification and is returned. A number of inputs which does not match the key, or points
+    /// which cannot be decoded, fail the verification.
+    pub fn verify_streaming<T: Field, E, I: IntoIterator<Item = Result<T, E>>>(
+        vk: VerificationKey,
+        proof: ProofPoints,
+        inputs: I,
+    ) -> Result<bool, E> {
+        let vk: VerifyingKey<T::BellmanEngine> = match vk.to_bellman::<T>() {
+            Ok(vk) => vk,
+            Err(_) => return Ok(false),
+        };
+
+        let proof: BellmanProof<T::BellmanEngine> = match proof.into_bellman::<T>() {
+            Ok(proof) => proof,
+            Err(_) => return Ok(false),
+        };
+
+        verify_bellman(&PreparedVerificationKey::from_bellman(&vk), &proof, inputs)
+    }
+
+    /// Verifies a proof against public inputs which are already decoded. Unlike
+    /// `ProofSystem::verify`, this reports invalid points as errors instead of failing the
+    /// verification.
+    pub fn verify_core<T: Field>(
+        vk: &VerificationKey,
+        proof: &ProofPoints,
+        inputs: &[T],
+    ) -> Result<bool, Error> {
+        let vk = vk.to_bellman::<T>()?;
+
+        let proof = proof.to_bellman::<T>()?;
+
+        verify_bellman(
+            &PreparedVerificationKey::from_bellman(&vk),
+            &proof,
+            inputs.iter().cloned().map(Ok),
+        )
+    }
+
+    /// Verifies a proof like `ProofSystem::verify` without taking ownership of the key, so that
+    /// many proofs can be verified against one key without cloning it. Invalid points, public
+    /// inputs or a wrong number of public inputs are reported as errors.
+    pub fn verify_ref<T: Field>(
+        vk: &VerificationKey,
+        proof: &Proof<ProofPoints>,
+    ) -> Result<bool, Error> {
+        proof.validate_against_vk::<T>(vk)?;
+
+        let inputs = proof
+            .inputs
+            .iter()
+            .map(|i| parse_public_input::<T>(i))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        G16::verify_core(vk, &proof.proof, &inputs)
+    }
+
+    /// Verifies a proof like `verify_ref`, also requiring its public inputs to be exactly
+    /// `expected`, e.g. a previously committed value, so that a valid proof of another statement
+    /// is not accepted. Returns `Ok(false)` if the proof does not verify, and
+    /// `Error::InputCountMismatch` or `Error::UnexpectedPublicInput` if its inputs are not the
+    /// expected ones, whether the proof verifies or not.
+    pub fn verify_with_expected_inputs<T: Field>(
+        vk: &VerificationKey,
+        proof: &Proof<ProofPoints>,
+        expected: &[T],
+    ) -> Result<bool, Error> {
+        if proof.inputs.len() != expected.len() {
+            return Err(Error::InputCountMismatch {
+                expected: expected.len(),
+                found: proof.inputs.len(),
+            });
+        }
+
+        for (index, (input, expected)) in proof.inputs.iter().zip(expected).enumerate() {
+            if &parse_public_input::<T>(input)? != expected {
+                return Err(Error::UnexpectedPublicInput { index });
+            }
+        }
+
+        G16::verify_ref::<T>(vk, proof)
+    }
+
+    /// Verifies a proof like `verify_ref`, reading the key and the proof as JSON from readers, so
+    /// that neither is buffered in an intermediate `String`
+    pub fn verify_from_readers<T: Field, R1: Read, R2: Read>(
+        vk_json: R1,
+        proof_json: R2,
+    ) -> Result<bool, Error> {
+        let invalid_json = |e: serde_json::Error| Error::InvalidJson(e.to_string());
+
+        let vk: VerificationKey = serde_json::from_reader(vk_json).map_err(invalid_json)?;
+        let proof: Proof<ProofPoints> =
+            serde_json::from_reader(proof_json).map_err(invalid_json)?;
+
+        G16::verify_ref::<T>(&vk, &proof)
+    }
+
+    /// Recomputes the terms of the pairing product checked by the verification one by one, to tell
+    /// a malformed proof or key apart from a well-formed proof which does not verify, for instance
+    /// because of wrong public inputs. Public inputs which cannot be decoded, or a wrong number of
+    /// them, are reported as errors.
+    pub fn verify_diagnostic<T: Field>(
+        vk: &VerificationKey,
+        proof: &Proof<ProofPoints>,
+    ) -> Result<VerificationDiagnostic, Error> {
+        proof.validate_against_vk::<T>(vk)?;
+
+        let inputs = proof
+            .inputs
+            .iter()
+            .map(|i| parse_public_input::<T>(i))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let g1 = |name: &str, g1: &G1Affine| {
+            decode_finite_g1::<T>(name, g1).and_then(|p| in_subgroup(name, p))
+        };
+        let g2 = |name: &str, g2: &G2Affine| {
+            decode_finite_g2::<T>(name, g2, G2Order::default()).and_then(|p| in_subgroup(name, p))
+        };
+        let negate = |mut p: <T::BellmanEngine as Engine>::G1Affine| {
+            p.negate();
+            p
+        };
+
+        let vk_x = vk
+            .gamma_abc
+            .iter()
+            .enumerate()
+            .map(|(i, g1)| {
+                let name = format!("gamma_abc[{}]", i);
+                decode_g1::<T>(&name, g1).and_then(|p| in_subgroup(&name, p))
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(|gamma_abc| {
+                let mut vk_x = gamma_abc[0].into_projective();
+                for (input, g1) in inputs.iter().zip(&gamma_abc[1..]) {
+                    vk_x.add_assign(&g1.mul(input.clone().into_bellman().into_repr()));
+                }
+                vk_x.into_affine()
+            });
+
+        let pairs = vec![
+            ("e(a, b)", g1("a", &proof.proof.a), g2("b", &proof.proof.b)),
+            ("e(-vk_x, gamma)", vk_x.map(negate), g2("gamma", &vk.gamma)),
+            (
+                "e(-c, delta)",
+                g1("c", &proof.proof.c).map(negate),
+                g2("delta", &vk.delta),
+            ),
+            (
+                "e(-alpha, beta)",
+                g1("alpha", &vk.alpha).map(negate),
+                g2("beta", &vk.beta),
+            ),
+        ];
+
+        let terms = pairs
+            .iter()
+            .map(|(name, p, q)| PairingTerm {
+                name,
+                error: p.clone().and(q.clone()).err(),
+            })
+            .collect();
+
+        let prepared = pairs
+            .into_iter()
+            .map(|(_, p, q)| Ok((p?.prepare(), q?.prepare())))
+            .collect::<Result<Vec<_>, Error>>();
+
+        let product = prepared.ok().map(|prepared| {
+            <T::BellmanEngine as Engine>::final_exponentiation(
+                &<T::BellmanEngine as Engine>::miller_loop(
+                    prepared
+                        .iter()
+                        .map(|(p, q)| (p, q))
+                        .collect::<Vec<_>>()
+                        .iter(),
+                ),
+            )
+            .unwrap()
+        });
+
+        Ok(VerificationDiagnostic {
+            terms,
+            verified: product == Some(<T::BellmanEngine as Engine>::Fqk::one()),
+            product: product.map(|product| T::fqk_to_bytes(&product)),
+        })
+    }
+
+    /// Verifies a proof of a program whose only public input packs `inputs` with
+    /// `pack_public_inputs`. A key with another number of public inputs is reported as an error.
+    pub fn verify_packed<T: Field>(
+        vk: &VerificationKey,
+        proof: &ProofPoints,
+        inputs: &[T],
+    ) -> Result<bool, Error> {
+        let expected = vk.expected_public_input_count();
+        if expected != 1 {
+            return Err(Error::InputCountMismatch { expected, found: 1 });
+        }
+
+        G16::verify_core(vk, proof, &[pack_public_inputs(inputs)])
+    }
+
+    /// Verifies a proof against the values of the public variables of its program, which are
+    /// associated to the `gamma_abc` points of `vk` by `layout` rather than by their position
+    pub fn verify_with_layout<T: Field>(
+        vk: &VerificationKey,
+        proof: &ProofPoints,
+        layout: &ir::PublicInputLayout,
+        inputs: &HashMap<FlatVariable, T>,
+    ) -> Result<bool, Error> {
+        let expected = vk.expected_public_input_count();
+        if layout.len() != expected {
+            return Err(Error::InputCountMismatch {
+                expected,
+                found: layout.len(),
+            });
+        }
+
+        let inputs = layout
+            .order(inputs)
+            .map_err(|v| Error::MissingPublicInput(v.to_string()))?;
+
+        G16::verify_core(vk, proof, &inputs)
+    }
+
+    /// Prepares `vk` for the verification and serializes it, see `PreparedVerificationKey`
+    pub fn prepared_vk_to_bytes<T: Field>(vk: &VerificationKey) -> Result<Vec<u8>, Error> {
+        Ok(PreparedVerificationKey::<T>::prepare(vk)?.to_bytes())
+    }
+
+    pub fn prepared_vk_from_bytes<T: Field>(
+        bytes: &[u8],
+    ) -> Result<PreparedVerificationKey<T>, Error> {
+        PreparedVerificationKey::from_bytes(bytes)
+    }
+
+    /// Verifies a proof against a prepared verification key. Unlike `ProofSystem::verify`, invalid
+    /// points, public inputs or a wrong number of public inputs are reported as errors.
+    pub fn verify_prepared<T: Field>(
+        pvk: &PreparedVerificationKey<T>,
+        proof: Proof<ProofPoints>,
+    ) -> Result<bool, Error> {
+        let expected = pvk.ic.len().saturating_sub(1);
+        if proof.inputs.len() != expected {
+            return Err(Error::InputCountMismatch {
+                expected,
+                found: proof.inputs.len(),
+            });
+        }
+
+        let inputs = proof
+            .inputs
+            .iter()
+            .map(|i| parse_public_input::<T>(i))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let proof = proof.proof.into_bellman::<T>()?;
+
+        verify_bellman(pvk, &proof, inputs.into_iter().map(Ok))
+    }
+
+    /// Verifies a proof against the public inputs fixed in `partial` and the values of the other
+    /// inputs in `varying`, given in the order of `PartialVkX::varying_indices`. Invalid points or
+    /// a wrong number of varying inputs are reported as errors.
+    pub fn verify_with_partial<T: Field>(
+        partial: &PartialVkX<T>,
+        proof: &ProofPoints,
+        varying: &[T],
+    ) -> Result<bool, Error> {
+        if varying.len() != partial.varying.len() {
+            return Err(Error::InputCountMismatch {
+                expected: partial.varying.len(),
+                found: varying.len(),
+            });
+        }
+
+        let proof = proof.to_bellman::<T>()?;
+
+        let mut vk_x = partial.acc.into_projective();
+        for (index, value) in partial.varying.iter().zip(varying) {
+            vk_x.add_assign(
+                &partial.pvk.ic[index + 1].mul(value.clone().into_bellman().into_repr()),
+            );
+        }
+
+        Ok(check_pairing(&partial.pvk, &proof, &vk_x.into_affine()))
+    }
+
+    /// Verifies a proof against a prepared verification key serialized with `prepared_vk_to_bytes`
+    pub fn verify_with_prepared_bytes<T: Field>(
+        pvk_bytes: &[u8],
+        proof: Proof<ProofPoints>,
+    ) -> Result<bool, Error> {
+        G16::verify_prepared(&G16::prepared_vk_from_bytes::<T>(pvk_bytes)?, proof)
+    }
+
+    /// Verifies each proof against its own key, in order. Returns `Ok(true)` if all of them verify,
+    /// otherwise the index of the first pair which does not, with `Error::VerificationFailed` if
+    /// its proof is well-formed but invalid
+    pub fn verify_all<T: Field>(
+        pairs: &[(&VerificationKey, &Proof<ProofPoints>)],
+    ) -> Result<bool, (usize, Error)> {
+        for (index, (vk, proof)) in pairs.iter().enumerate() {
+            match G16::verify_ref::<T>(vk, proof) {
+                Ok(true) => {}
+                Ok(false) => return Err((index, Error::VerificationFailed)),
+                Err(e) => return Err((index, e)),
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+/// A verification key with the pairing of `alpha` and `beta` computed ahead of the verification,
+/// like bellman's `PreparedVerifyingKey`, which cannot be serialized.
+///
+/// Its serialization holds the negated `gamma` and `delta`, the `gamma_abc` points, and the
+/// pairing of `alpha` and `beta`, so that reading it back does not compute any pairing.
+#[derive(Debug, Clone)]
+pub struct PreparedVerificationKey<T: Field> {
+    alpha_beta: <T::BellmanEngine as Engine>::Fqk,
+    neg_gamma: <T::BellmanEngine as Engine>::G2Affine,
+    neg_delta: <T::BellmanEngine as Engine>::G2Affine,
+    ic: Vec<<T::BellmanEngine as Engine>::G1Affine>,
+}
+
+impl<T: Field> PartialEq for PreparedVerificationKey<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.alpha_beta == other.alpha_beta
+            && self.neg_gamma == other.neg_gamma
+            && self.neg_delta == other.neg_delta
+            && self.ic == other.ic
+    }
+}
+
+impl<T: Field> PreparedVerificationKey<T> {
+    pub fn prepare(vk: &VerificationKey) -> Result<Self, Error> {
+        Ok(Self::from_bellman(&vk.to_bellman::<T>()?))
+    }
+
+    pub(super) fn from_bellman(vk: &VerifyingKey<T::BellmanEngine>) -> Self {
+        let mut neg_gamma = vk.gamma_g2;
+        neg_gamma.negate();
+        let mut neg_delta = vk.delta_g2;
+        neg_delta.negate();
+
+        PreparedVerificationKey {
+            alpha_beta: <T::BellmanEngine as Engine>::pairing(vk.alpha_g1, vk.beta_g2),
+            neg_gamma,
+            neg_delta,
+            ic: vk.ic.clone(),
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![];
+        bytes.extend_from_slice(self.neg_gamma.into_uncompressed().as_ref());
+        bytes.extend_from_slice(self.neg_delta.into_uncompressed().as_ref());
+        bytes.extend_from_slice(&(self.ic.len() as u32).to_be_bytes());
+        for ic in &self.ic {
+            bytes.extend_from_slice(ic.into_uncompressed().as_ref());
+        }
+        bytes.extend_from_slice(&T::fqk_to_bytes(&self.alpha_beta));
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        fn read_point<P: CurveAffine>(bytes: &mut &[u8], name: &str) -> Result<P, Error> {
+            let mut encoded = P::Uncompressed::empty();
+            let len = encoded.as_ref().len();
+            if bytes.len() < len {
+                return Err(Error::InvalidPreparedKey(format!(
+                    "missing point `{}`",
+                    name
+                )));
+            }
+            encoded.as_mut().copy_from_slice(&bytes[..len]);
+            *bytes = &bytes[len..];
+
+            encoded.into_affine().map_err(|e| Error::InvalidPoint {
+                point: name.to_string(),
+                reason: e.to_string(),
+            })
+        }
+
+        let mut bytes = bytes;
+
+        let neg_gamma = read_point(&mut bytes, "gamma")?;
+        let neg_delta = read_point(&mut bytes, "delta")?;
+
+        if bytes.len() < 4 {
+            return Err(Error::InvalidPreparedKey(String::from(
+                "missing number of gamma_abc points",
+            )));
+        }
+        let ic_len = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize;
+        bytes = &bytes[4..];
+
+        let ic = (0..ic_len)
+            .map(|i| read_point(&mut bytes, &format!("gamma_abc[{}]", i)))
+            .collect::<Result<_, _>>()?;
+
+        let alpha_beta = T::fqk_from_bytes(bytes).map_err(|_| {
+            Error::InvalidPreparedKey(String::from("invalid pairing of alpha and beta"))
+        })?;
+
+        Ok(PreparedVerificationKey {
+            alpha_beta,
+            neg_gamma,
+            neg_delta,
+            ic,
+        })
+    }
+}
+
+/// Checks the Groth16 verification equation like `bellman::groth16::verify_proof`, accumulating the
+/// public inputs as they are consumed. A number of inputs which does not match the key fails the
+/// verification.
+pub(super) fn verify_bellman<T: Field, E, I: IntoIterator<Item = Result<T, E>>>(
+    pvk: &PreparedVerificationKey<T>,
+    proof: &BellmanProof<T::BellmanEngine>,
+    inputs: I,
+) -> Result<bool, E> {
+    let mut ic = pvk.ic.iter();
+    let mut acc = match ic.next() {
+        Some(ic_0) => ic_0.into_projective(),
+        None => return Ok(false),
+    };
+
+    for input in inputs {
+        let input = input?;
+        match ic.next() {
+            Some(b) => acc.add_assign(&b.mul(input.into_bellman().into_repr())),
+            None => return Ok(false),
+        }
+    }
+
+    if ic.next().is_some() {
+        return Ok(false);
+    }
+
+    Ok(check_pairing(pvk, proof, &acc.into_affine()))
+}
+
+/// Checks the Groth16 verification equation for the linear combination `vk_x` of the public inputs
+fn check_pairing<T: Field>(
+    pvk: &PreparedVerificationKey<T>,
+    proof: &BellmanProof<T::BellmanEngine>,
+    vk_x: &<T::BellmanEngine as Engine>::G1Affine,
+) -> bool {
+    <T::BellmanEngine as Engine>::final_exponentiation(&<T::BellmanEngine as Engine>::miller_loop(
+        [
+            (&proof.a.prepare(), &proof.b.prepare()),
+            (&vk_x.prepare(), &pvk.neg_gamma.prepare()),
+            (&proof.c.prepare(), &pvk.neg_delta.prepare()),
+        ]
+        .iter(),
+    ))
+    .unwrap()
+        == pvk.alpha_beta
+}
+
+/// The part of `vk_x` contributed by public inputs which are the same for many proofs, returned by
+/// `VerificationKey::precompute_fixed_inputs`. Verifying a proof with `G16::verify_with_partial`
+/// only adds the contribution of the other inputs.
+#[derive(Debug, Clone)]
+pub struct PartialVkX<T: Field> {
+    pvk: PreparedVerificationKey<T>,
+    /// `gamma_abc[0]` plus the fixed inputs times their `gamma_abc` points
+    acc: <T::BellmanEngine as Engine>::G1Affine,
+    /// the indices of the inputs which are not fixed, in increasing order
+    varying: Vec<usize>,
+}
+
+impl VerificationKey {
+    /// Computes the contribution of the public inputs in `fixed` to `vk_x`, given as pairs of the
+    /// index of an input and its value, so that proofs whose other inputs vary can be verified
+    /// with `G16::verify_with_partial` without recomputing it.
+    ///
+    /// Fails with `Error::InvalidPoint` if a point of the key is not on the curve of `T`, with
+    /// `Error::InputCountMismatch` if an index is not smaller than `expected_public_input_count`
+    /// and with `Error::DuplicateFixedInput` if an index is given twice.
+    pub fn precompute_fixed_inputs<T: Field>(
+        &self,
+        fixed: &[(usize, T)],
+    ) -> Result<PartialVkX<T>, Error> {
+        let pvk = PreparedVerificationKey::<T>::prepare(self)?;
+
+        let count = self.expected_public_input_count();
+        let mut is_fixed = vec![false; count];
+        let mut acc = pvk.ic[0].into_projective();
+        for (index, value) in fixed {
+            if *index >= count {
+                return Err(Error::InputCountMismatch {
+                    expected: count,
+                    found: index + 1,
+                });
+            }
+            if is_fixed[*index] {
+                return Err(Error::DuplicateFixedInput { index: *index });
+            }
+            is_fixed[*index] = true;
+
+            acc.add_assign(&pvk.ic[index + 1].mul(value.clone().into_bellman().into_repr()));
+        }
+
+        Ok(PartialVkX {
+            acc: acc.into_affine(),
+            varying: (0..count).filter(|i| !is_fixed[*i]).collect(),
+            pvk,
+        })
+    }
+}
+
+impl<T: Field> PartialVkX<T> {
+    /// The indices of the public inputs which are not fixed, in the order in which their values are
+    /// passed to `G16::verify_with_partial`
+    pub fn varying_indices(&self) -> &[usize] {
+        &self.varying
+    }
+}
+
+/// The stage of the verification at which a proof was rejected
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum VerificationStage {
+    /// decoding the public inputs and matching their number against the key
+    Inputs,
+    /// decoding the points of the key and of the proof
+    Points,
+    /// the pairing check, which rejected well-formed inputs and points
+    Pairing,
+}
+
+/// A self-contained description of a failed verification, returned by `G16::verify_with_report`
+/// so that it can be attached to a bug report
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct FailureReport {
+    /// the key and the proof, with the scheme and the curve they were verified on
+    pub bundle: VerifiableBundle,
+    /// the number of public inputs expected by the key
+    pub expected_public_input_count: usize,
+    /// the public inputs of the proof as decimal strings. If an input cannot be decoded, only the
+    /// inputs preceding it are included
+    pub inputs: Vec<String>,
+    /// the version of `zokrates_core` which ran the verification
+    pub zokrates_version: String,
+    pub stage: VerificationStage,
+    /// the error which rejected the proof, if it was rejected before the pairing check
+    pub error: Option<String>,
+}
+
+impl FailureReport {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+impl G16 {
+    /// Verifies a proof like `verify_ref`, returning a `FailureReport` with everything needed to
+    /// reproduce the verification if the proof is rejected
+    pub fn verify_with_report<T: Field>(
+        vk: &VerificationKey,
+        proof: &Proof<ProofPoints>,
+    ) -> Result<(), FailureReport> {
+        let mut inputs = vec![];
+
+        let report = |stage: VerificationStage, error: Option<Error>, inputs: &[T]| FailureReport {
+            bundle: VerifiableBundle::new::<T>(vk.clone(), proof.clone()),
+            expected_public_input_count: vk.expected_public_input_count(),
+            inputs: inputs.iter().map(|i| i.to_dec_string()).collect(),
+            zokrates_version: ZOKRATES_VERSION.to_string(),
+            stage,
+            error: error.map(|e| e.to_string()),
+        };
+
+        for input in &proof.inputs {
+            match parse_public_input::<T>(input) {
+                Ok(input) => inputs.push(input),
+                Err(e) => return Err(report(VerificationStage::Inputs, Some(e), &inputs)),
+            }
+        }
+
+        let expected = vk.expected_public_input_count();
+        if inputs.len() != expected {
+            let e = Error::InputCountMismatch {
+                expected,
+                found: inputs.len(),
+            };
+            return Err(report(VerificationStage::Inputs, Some(e), &inputs));
+        }
+
+        let (bellman_vk, bellman_proof) = match vk
+            .to_bellman::<T>()
+            .and_then(|vk| Ok((vk, proof.proof.to_bellman::<T>()?)))
+        {
+            Ok(decoded) => decoded,
+            Err(e) => return Err(report(VerificationStage::Points, Some(e), &inputs)),
+        };
+
+        match verify_bellman(
+            &PreparedVerificationKey::from_bellman(&bellman_vk),
+            &bellman_proof,
+            inputs.iter().cloned().map(Ok::<_, ()>),
+        ) {
+            Ok(true) => Ok(()),
+            _ => Err(report(VerificationStage::Pairing, None, &inputs)),
+        }
+    }
+}
+
+/// The fields of a proof in JSON which identify its proving scheme and its curve, as in a
+/// `VerifiableBundle`. Proofs written by ZoKrates name neither, so untagged proofs are G16 proofs
+/// for the default curve, `bn128`.
+#[derive(Deserialize)]
+struct ProofTag {
+    #[serde(default = "default_scheme")]
+    scheme: String,
+    #[serde(default = "default_curve")]
+    curve: String,
+}
+
+fn default_scheme() -> String {
+    G16_SCHEME.to_string()
+}
+
+fn default_curve() -> String {
+    Bn128Field::name().to_string()
+}
+
+/// Verifies a proof against a key, both in JSON, with the proving scheme and the curve taken from
+/// the `scheme` and `curve` fields of the proof, see `ProofTag`. G16 is the only supported scheme,
+/// other schemes fail with `Error::UnsupportedScheme` before the key and the proof are read.
+pub fn verify_tagged(vk_json: &str, proof_json: &str) -> Result<bool, Error> {
+    let invalid_json = |e: serde_json::Error| Error::InvalidJson(e.to_string());
+
+    let tag: ProofTag = serde_json::from_str(proof_json).map_err(invalid_json)?;
+
+    match tag.scheme.as_str() {
+        G16_SCHEME => VerifiableBundle {
+            scheme: tag.scheme,
+            curve: tag.curve,
+            vk: serde_json::from_str(vk_json).map_err(invalid_json)?,
+            proof: serde_json::from_str(proof_json).map_err(invalid_json)?,
+        }
+        .verify(),
+        _ => Err(Error::UnsupportedScheme(tag.scheme)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::flat_absy::FlatVariable;
+    use crate::ir::{Function, Interpreter, LinComb, Prog, QuadComb, Statement};
+    use crate::proof_system::bellman::groth16::setup_and_prove;
+    use crate::proof_system::bellman::rand::{ChaChaRng, SeedableRng};
+    use crate::proof_system::identity_program;
+    use crate::proof_system::ProofSystem;
+    use zokrates_field::{Bls12Field, Bn128Field};
+
+    #[test]
+    fn validate_against_vk() {
+        let (keypair, proof) = setup_and_prove(42);
+
+        assert_eq!(proof.validate_against_vk::<Bn128Field>(&keypair.vk), Ok(()));
+
+        let mut too_few = proof.clone();
+        too_few.inputs.pop();
+        assert_eq!(
+            too_few.validate_against_vk::<Bn128Field>(&keypair.vk),
+            Err(Error::InputCountMismatch {
+                expected: 2,
+                found: 1
+            })
+        );
+        assert!(!<G16 as ProofSystem<Bn128Field>>::verify(
+            keypair.vk.clone(),
+            too_few
+        ));
+
+        let mut unparsable = proof;
+        unparsable.inputs[0] = String::from("0xzz");
+        assert_eq!(
+            unparsable.validate_against_vk::<Bn128Field>(&keypair.vk),
+            Err(Error::InvalidPublicInput {
+                value: String::from("0xzz"),
+                reason: String::from("not a hex encoded bn128 value")
+            })
+        );
+        assert!(!<G16 as ProofSystem<Bn128Field>>::verify(
+            keypair.vk, unparsable
+        ));
+    }
+
+    #[test]
+    fn check_raw_consistency() {
+        let program = identity_program();
+
+        let keypair = G16::setup(program.clone());
+
+        let interpreter = Interpreter::default();
+
+        let witness = interpreter
+            .execute(&program, &vec![Bn128Field::from(42)])
+            .unwrap();
+
+        let other_witness = interpreter
+            .execute(&program, &vec![Bn128Field::from(43)])
+            .unwrap();
+
+        let proof = G16::generate_proof(program.clone(), witness, keypair.pk.clone());
+        let other = G16::generate_proof(program, other_witness, keypair.pk);
+
+        assert_eq!(proof.check_raw_consistency::<Bn128Field>(), Ok(()));
+        assert_eq!(
+            proof
+                .clone()
+                .without_raw()
+                .check_raw_consistency::<Bn128Field>(),
+            Ok(())
+        );
+
+        // the points of one proof with the raw encoding of another
+        let mut mismatched = proof.clone();
+        mismatched.raw = other.raw.clone();
+        assert_eq!(
+            mismatched.check_raw_consistency::<Bn128Field>(),
+            Err(Error::RawMismatch {
+                point: String::from("a")
+            })
+        );
+
+        let mut truncated = proof.clone();
+        truncated.raw.truncate(proof.raw.len() - 2);
+        assert!(matches!(
+            truncated.check_raw_consistency::<Bn128Field>(),
+            Err(Error::InvalidRaw(_))
+        ));
+
+        let mut extended = proof.clone();
+        extended.raw.push_str("00");
+        assert!(matches!(
+            extended.check_raw_consistency::<Bn128Field>(),
+            Err(Error::InvalidRaw(_))
+        ));
+
+        // the mismatch only fails the verification in strict mode, as the points are valid
+        let strict = VerifyOptions {
+            check_raw: true,
+            ..VerifyOptions::default()
+        };
+        assert!(G16::verify_with_options::<Bn128Field>(
+            keypair.vk.clone(),
+            mismatched.clone(),
+            &VerifyOptions::default()
+        ));
+        assert!(!G16::verify_with_options::<Bn128Field>(
+            keypair.vk.clone(),
+            mismatched,
+            &strict
+        ));
+        assert!(G16::verify_with_options::<Bn128Field>(
+            keypair.vk, proof, &strict
+        ));
+    }
+
+    #[test]
+    fn resync_raw() {
+        let program = identity_program();
+
+        let keypair = G16::setup(program.clone());
+        let witness = Interpreter::default()
+            .execute(&program, &vec![Bn128Field::from(42)])
+            .unwrap();
+
+        let prove = |seed: &[u32]| {
+            G16::generate_proof_with_rng(
+                program.clone(),
+                witness.clone(),
+                keypair.pk.clone(),
+                &mut ChaChaRng::from_seed(seed),
+                Some(G16::acknowledge_malleability()),
+                &mut std::io::sink(),
+            )
+        };
+
+        let decode_raw = |raw: &str| {
+            ProofPoints::from_bellman::<Bn128Field>(
+                &BellmanProof::read(&hex::decode(raw).unwrap()[..]).unwrap(),
+            )
+        };
+
+        let proof = prove(&[1, 2, 3]);
+        let other = prove(&[4, 5, 6]);
+
+        // canonicalize points written with uppercase hex digits
+        let mut uppercase = proof.clone();
+        uppercase.proof.a.0 = format!("0x{}", uppercase.proof.a.0[2..].to_uppercase());
+        uppercase.proof = ProofPoints::from_bellman::<Bn128Field>(
+            &uppercase
+                .proof
+                .clone()
+                .into_bellman::<Bn128Field>()
+                .unwrap(),
+        );
+        uppercase.resync_raw::<Bn128Field>().unwrap();
+
+        assert_eq!(decode_raw(&uppercase.raw), uppercase.proof);
+        assert_eq!(uppercase, proof);
+
+        // replacing the points makes the raw bytes stale until they are regenerated
+        let mut replaced = proof.clone();
+        replaced.proof = other.proof.clone();
+        assert_ne!(decode_raw(&replaced.raw), replaced.proof);
+
+        replaced.resync_raw::<Bn128Field>().unwrap();
+        assert_eq!(decode_raw(&replaced.raw), other.proof);
+        assert_eq!(replaced.raw, other.raw);
+
+        // invalid points are rejected, leaving the proof untouched
+        let mut invalid = proof.clone();
+        invalid.proof.a = G1Affine::new(String::from("0x01"), String::from("0x01"));
+        assert!(invalid.resync_raw::<Bn128Field>().is_err());
+        assert_eq!(invalid.raw, proof.raw);
+    }
+
+    #[test]
+    fn prepared_vk_bytes() {
+        let (keypair, proof) = setup_and_prove(42);
+
+        let bytes = G16::prepared_vk_to_bytes::<Bn128Field>(&keypair.vk).unwrap();
+        let pvk = G16::prepared_vk_from_bytes::<Bn128Field>(&bytes).unwrap();
+        let fresh = PreparedVerificationKey::<Bn128Field>::prepare(&keypair.vk).unwrap();
+        assert_eq!(pvk, fresh);
+        assert_eq!(pvk.to_bytes(), bytes);
+
+        let mut tampered = proof.clone();
+        tampered.inputs[1] = format!("0x{:064x}", 43);
+
+        for proof in vec![proof, tampered] {
+            let expected =
+                <G16 as ProofSystem<Bn128Field>>::verify(keypair.vk.clone(), proof.clone());
+            assert_eq!(G16::verify_prepared(&fresh, proof.clone()), Ok(expected));
+            assert_eq!(
+                G16::verify_with_prepared_bytes::<Bn128Field>(&bytes, proof),
+                Ok(expected)
+            );
+        }
+
+        assert!(G16::prepared_vk_from_bytes::<Bn128Field>(&bytes[..bytes.len() - 1]).is_err());
+        assert!(G16::prepared_vk_from_bytes::<Bn128Field>(&bytes[..100]).is_err());
+        assert!(G16::prepared_vk_from_bytes::<Bls12Field>(&bytes).is_err());
+    }
+
+    #[test]
+    fn verify_tagged() {
+        let (keypair, proof) = setup_and_prove(42);
+
+        let vk_json = serde_json::to_string(&keypair.vk).unwrap();
+        let tagged = |scheme: &str| {
+            let mut proof_json = serde_json::to_value(&proof).unwrap();
+            proof_json["scheme"] = serde_json::json!(scheme);
+            proof_json.to_string()
+        };
+
+        assert_eq!(super::verify_tagged(&vk_json, &tagged("g16")), Ok(true));
+
+        let unknown = super::verify_tagged(&vk_json, &tagged("plonk"));
+        assert_eq!(
+            unknown,
+            Err(Error::UnsupportedScheme(String::from("plonk")))
+        );
+        assert_eq!(
+            unknown.unwrap_err().to_string(),
+            "Unsupported proving scheme `plonk`, expected `g16`"
+        );
+
+        // the proofs written by ZoKrates are untagged
+        assert_eq!(
+            super::verify_tagged(&vk_json, &proof.to_json_pretty()),
+            Ok(true)
+        );
+        assert_eq!(
+            super::verify_tagged(&vk_json, &proof.to_json_compact()),
+            Ok(true)
+        );
+    }
+
+    #[test]
+    fn verify_verbose() {
+        let (keypair, proof) = setup_and_prove(42);
+
+        assert_eq!(
+            G16::verify_verbose(keypair.vk.clone(), proof.clone()),
+            VerifyOutcome {
+                verified: true,
+                inputs: vec![Bn128Field::from(42), Bn128Field::from(42)],
+                incompatible_version: None
+            }
+        );
+
+        let mut tampered = proof.clone();
+        tampered.inputs[1] = String::from("0x2b");
+        assert_eq!(
+            G16::verify_verbose(keypair.vk.clone(), tampered),
+            VerifyOutcome {
+                verified: false,
+                inputs: vec![Bn128Field::from(42), Bn128Field::from(43)],
+                incompatible_version: None
+            }
+        );
+
+        let mut unparsable = proof.clone();
+        unparsable.inputs[1] = String::from("0xzz");
+        assert_eq!(
+            G16::verify_verbose(keypair.vk.clone(), unparsable),
+            VerifyOutcome {
+                verified: false,
+                inputs: vec![Bn128Field::from(42)],
+                incompatible_version: None
+            }
+        );
+
+        // an incompatible version is reported without failing the verification
+        let newer = Proof {
+            producer_version: Some(String::from("999.0.0")),
+            ..proof
+        };
+        let outcome = G16::verify_verbose::<Bn128Field>(keypair.vk, newer);
+        assert!(outcome.verified);
+        match outcome.incompatible_version {
+            Some(Error::IncompatibleVersion(reason)) => assert!(reason.contains("999.0.0")),
+            e => panic!("unexpected incompatible version {:?}", e),
+        }
+    }
+
+    #[test]
+    fn verify_streaming() {
+        let (keypair, proof) = setup_and_prove(42);
+        let vk = keypair.vk;
+
+        let streamed = |inputs: Vec<u32>| {
+            G16::verify_streaming(
+                vk.clone(),
+                proof.proof.clone(),
+                inputs.into_iter().map(|i| Ok::<_, ()>(Bn128Field::from(i))),
+            )
+        };
+
+        assert_eq!(
+            streamed(vec![42, 42]),
+            Ok(<G16 as ProofSystem<Bn128Field>>::verify(
+                vk.clone(),
+                proof.clone()
+            ))
+        );
+        assert_eq!(streamed(vec![42, 42]), Ok(true));
+        assert_eq!(streamed(vec![42, 43]), Ok(false));
+        assert_eq!(streamed(vec![42]), Ok(false));
+        assert_eq!(streamed(vec![42, 42, 42]), Ok(false));
+
+        let failing = G16::verify_streaming(
+            vk.clone(),
+            proof.proof.clone(),
+            vec![Ok(Bn128Field::from(42)), Err("unreadable input")],
+        );
+        assert_eq!(failing, Err("unreadable input"));
+    }
+
+    #[test]
+    fn verify_with_report() {
+        let (keypair, proof) = setup_and_prove(42);
+
+        assert_eq!(
+            G16::verify_with_report::<Bn128Field>(&keypair.vk, &proof),
+            Ok(())
+        );
+
+        let mut wrong_input = proof.clone();
+        wrong_input.inputs[1] = format!("0x{:064x}", 43);
+        let report = G16::verify_with_report::<Bn128Field>(&keypair.vk, &wrong_input).unwrap_err();
+        assert_eq!(report.stage, VerificationStage::Pairing);
+        assert_eq!(report.error, None);
+        assert_eq!(report.expected_public_input_count, 2);
+        assert_eq!(report.inputs, vec!["42", "43"]);
+        assert_eq!(
+            report.bundle,
+            VerifiableBundle::new::<Bn128Field>(keypair.vk.clone(), wrong_input)
+        );
+        assert_eq!(report.zokrates_version, ZOKRATES_VERSION);
+
+        // the report can be shared as JSON
+        let json: serde_json::Value = serde_json::from_str(&report.to_json()).unwrap();
+        assert_eq!(json["stage"], "pairing");
+        assert_eq!(json["expected_public_input_count"], 2);
+        assert_eq!(json["inputs"], serde_json::json!(["42", "43"]));
+
+        let mut missing_input = proof.clone();
+        missing_input.inputs.pop();
+        let report =
+            G16::verify_with_report::<Bn128Field>(&keypair.vk, &missing_input).unwrap_err();
+        assert_eq!(report.stage, VerificationStage::Inputs);
+        assert_eq!(report.inputs, vec!["42"]);
+        assert_eq!(
+            report.error,
+            Some(
+                Error::InputCountMismatch {
+                    expected: 2,
+                    found: 1
+                }
+                .to_string()
+            )
+        );
+
+        let mut invalid_point = proof;
+        invalid_point.proof.c = G1Affine::new(String::from("0x1"), String::from("0x1"));
+        let report =
+            G16::verify_with_report::<Bn128Field>(&keypair.vk, &invalid_point).unwrap_err();
+        assert_eq!(report.stage, VerificationStage::Points);
+        assert_eq!(report.inputs, vec!["42", "42"]);
+        assert!(report.error.is_some());
+    }
+
+    #[test]
+    fn verify_from_readers() {
+        use std::io::Cursor;
+
+        let (keypair, proof) = setup_and_prove(42);
+
+        let vk_json = serde_json::to_vec(&keypair.vk).unwrap();
+
+        assert_eq!(
+            G16::verify_from_readers::<Bn128Field, _, _>(
+                Cursor::new(&vk_json),
+                Cursor::new(proof.to_json_pretty())
+            ),
+            Ok(<G16 as ProofSystem<Bn128Field>>::verify(
+                keypair.vk.clone(),
+                proof.clone()
+            ))
+        );
+        assert_eq!(
+            G16::verify_from_readers::<Bn128Field, _, _>(
+                Cursor::new(&vk_json),
+                Cursor::new(proof.to_json_compact())
+            ),
+            Ok(true)
+        );
+
+        let mut wrong_input = proof.clone();
+        wrong_input.inputs[0] = format!("0x{:064x}", 43);
+        assert_eq!(
+            G16::verify_from_readers::<Bn128Field, _, _>(
+                Cursor::new(&vk_json),
+                Cursor::new(wrong_input.to_json_pretty())
+            ),
+            Ok(<G16 as ProofSystem<Bn128Field>>::verify(
+                keypair.vk,
+                wrong_input
+            ))
+        );
+
+        match G16::verify_from_readers::<Bn128Field, _, _>(
+            Cursor::new(&vk_json[..vk_json.len() - 1]),
+            Cursor::new(proof.to_json_pretty()),
+        ) {
+            Err(Error::InvalidJson(_)) => {}
+            r => panic!("unexpected result {:?}", r),
+        }
+    }
+
+    #[test]
+    fn verify_diagnostic() {
+        let (keypair, proof) = setup_and_prove(42);
+
+        let diagnostic = G16::verify_diagnostic::<Bn128Field>(&keypair.vk, &proof).unwrap();
+        assert!(diagnostic.verified);
+        assert!(diagnostic.terms.iter().all(|t| t.is_well_formed()));
+        assert_eq!(
+            diagnostic.terms.iter().map(|t| t.name).collect::<Vec<_>>(),
+            vec![
+                "e(a, b)",
+                "e(-vk_x, gamma)",
+                "e(-c, delta)",
+                "e(-alpha, beta)"
+            ]
+        );
+        assert_eq!(
+            diagnostic.product,
+            Some(Bn128Field::fqk_to_bytes(
+                &<<Bn128Field as Field>::BellmanEngine as Engine>::Fqk::one()
+            ))
+        );
+
+        // a well-formed `c` which does not belong to the proof
+        let mut tampered = proof.clone();
+        tampered.proof.c = proof.proof.a.clone();
+        let diagnostic = G16::verify_diagnostic::<Bn128Field>(&keypair.vk, &tampered).unwrap();
+        assert!(!diagnostic.verified);
+        assert!(diagnostic.terms.iter().all(|t| t.is_well_formed()));
+        assert!(diagnostic.product.is_some());
+        assert_ne!(
+            diagnostic.product,
+            Some(Bn128Field::fqk_to_bytes(
+                &<<Bn128Field as Field>::BellmanEngine as Engine>::Fqk::one()
+            ))
+        );
+
+        // a `c` which is not on the curve only invalidates its own term
+        let mut malformed = proof.clone();
+        malformed.proof.c = G1Affine::new(String::from("0x1"), String::from("0x1"));
+        let diagnostic = G16::verify_diagnostic::<Bn128Field>(&keypair.vk, &malformed).unwrap();
+        assert!(!diagnostic.verified);
+        assert_eq!(diagnostic.product, None);
+        assert_eq!(
+            diagnostic
+                .terms
+                .iter()
+                .map(|t| t.is_well_formed())
+                .collect::<Vec<_>>(),
+            vec![true, true, false, true]
+        );
+
+        let mut missing_input = proof;
+        missing_input.inputs.pop();
+        assert_eq!(
+            G16::verify_diagnostic::<Bn128Field>(&keypair.vk, &missing_input),
+            Err(Error::InputCountMismatch {
+                expected: 2,
+                found: 1
+            })
+        );
+    }
+
+    #[test]
+    fn verify_packed() {
+        // the program returns its private argument, which is the packed input
+        let program = Prog {
+            private: vec![true],
+            ..identity_program()
+        };
+
+        let keypair = G16::setup(program.clone());
+
+        let inputs = vec![Bn128Field::from(1), Bn128Field::from(2)];
+
+        let witness = Interpreter::default()
+            .execute(&program, &vec![pack_public_inputs(&inputs)])
+            .unwrap();
+
+        let proof = G16::generate_proof(program, witness, keypair.pk);
+
+        assert_eq!(
+            G16::verify_packed(&keypair.vk, &proof.proof, &inputs),
+            Ok(true)
+        );
+        assert_eq!(
+            G16::verify_packed(
+                &keypair.vk,
+                &proof.proof,
+                &[Bn128Field::from(2), Bn128Field::from(1)]
+            ),
+            Ok(false)
+        );
+
+        let mut two_inputs = keypair.vk.clone();
+        two_inputs.gamma_abc.push(two_inputs.gamma_abc[1].clone());
+        assert_eq!(
+            G16::verify_packed(&two_inputs, &proof.proof, &inputs),
+            Err(Error::InputCountMismatch {
+                expected: 2,
+                found: 1
+            })
+        );
+    }
+
+    #[test]
+    fn verify_core() {
+        let (keypair, proof) = setup_and_prove(42);
+
+        let inputs = vec![Bn128Field::from(42), Bn128Field::from(42)];
+        assert_eq!(
+            G16::verify_core(&keypair.vk, &proof.proof, &inputs),
+            Ok(<G16 as ProofSystem<Bn128Field>>::verify(
+                keypair.vk.clone(),
+                proof.clone()
+            ))
+        );
+        assert_eq!(
+            G16::verify_core(&keypair.vk, &proof.proof, &inputs),
+            Ok(true)
+        );
+        assert_eq!(
+            G16::verify_core(
+                &keypair.vk,
+                &proof.proof,
+                &[Bn128Field::from(42), Bn128Field::from(43)]
+            ),
+            Ok(false)
+        );
+
+        let mut invalid = proof.proof.clone();
+        invalid.a = G1Affine::new(String::from("0x1"), String::from("0x3"));
+        assert!(match G16::verify_core(&keypair.vk, &invalid, &inputs) {
+            Err(Error::InvalidPoint { point, .. }) => point == "a",
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn verify_strict_canonical() {
+        let (keypair, proof) = setup_and_prove(42);
+
+        let strict = VerifyOptions {
+            strict_canonical: true,
+            ..VerifyOptions::default()
+        };
+
+        assert!(G16::verify_with_options::<Bn128Field>(
+            keypair.vk.clone(),
+            proof.clone(),
+            &strict
+        ));
+
+        // 42 without zero padding
+        let mut unpadded_input = proof.clone();
+        unpadded_input.inputs[0] = String::from("0x2a");
+
+        assert!(<G16 as ProofSystem<Bn128Field>>::verify(
+            keypair.vk.clone(),
+            unpadded_input.clone()
+        ));
+        assert!(!G16::verify_with_options::<Bn128Field>(
+            keypair.vk.clone(),
+            unpadded_input,
+            &strict
+        ));
+
+        // the same point, with uppercase hex digits
+        let mut uppercase_point = proof.clone();
+        uppercase_point.proof.a.0 = format!("0x{}", uppercase_point.proof.a.0[2..].to_uppercase());
+
+        assert!(<G16 as ProofSystem<Bn128Field>>::verify(
+            keypair.vk.clone(),
+            uppercase_point.clone()
+        ));
+        assert!(!G16::verify_with_options::<Bn128Field>(
+            keypair.vk,
+            uppercase_point,
+            &strict
+        ));
+    }
+
+    #[test]
+    fn verify_with_layout() {
+        // ~out_0 = 2 * _0, so that the public inputs have different values
+        let program: Prog<Bn128Field> = Prog {
+            main: Function {
+                id: String::from("main"),
+                arguments: vec![FlatVariable::new(0)],
+                returns: vec![FlatVariable::public(0)],
+                statements: vec![Statement::Constraint(
+                    LinComb::summand(2, FlatVariable::new(0)).into(),
+                    FlatVariable::public(0).into(),
+                )],
+            },
+            private: vec![false],
+        };
+
+        let keypair = G16::setup(program.clone());
+
+        let interpreter = Interpreter::default();
+
+        let witness = interpreter
+            .execute(&program, &vec![Bn128Field::from(42)])
+            .unwrap();
+
+        let layout = program.public_input_layout();
+        let proof = G16::generate_proof(program, witness, keypair.pk);
+
+        let inputs: HashMap<_, _> = vec![
+            (FlatVariable::public(0), Bn128Field::from(84)),
+            (FlatVariable::new(0), Bn128Field::from(42)),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(
+            G16::verify_with_layout(&keypair.vk, &proof.proof, &layout, &inputs),
+            Ok(true)
+        );
+
+        let swapped: HashMap<_, _> = vec![
+            (FlatVariable::new(0), Bn128Field::from(84)),
+            (FlatVariable::public(0), Bn128Field::from(42)),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(
+            G16::verify_with_layout(&keypair.vk, &proof.proof, &layout, &swapped),
+            Ok(false)
+        );
+
+        let mut missing = inputs;
+        missing.remove(&FlatVariable::public(0));
+        assert_eq!(
+            G16::verify_with_layout(&keypair.vk, &proof.proof, &layout, &missing),
+            Err(Error::MissingPublicInput(String::from("~out_0")))
+        );
+    }
+
+    #[test]
+    fn without_raw() {
+        let (keypair, proof) = setup_and_prove(42);
+        let stripped = proof.clone().without_raw();
+
+        assert!(stripped.raw.is_empty());
+        assert!(stripped.to_json_compact().len() < proof.to_json_compact().len());
+        assert!(<G16 as ProofSystem<Bn128Field>>::verify(
+            keypair.vk,
+            serde_json::from_str(&stripped.to_json_compact()).unwrap()
+        ));
+
+        let mut restored = stripped;
+        restored.resync_raw::<Bn128Field>().unwrap();
+        assert_eq!(restored, proof);
+    }
+
+    #[test]
+    fn public_input_hash() {
+        let proof = |inputs: Vec<&str>| {
+            Proof::new(
+                ProofPoints {
+                    a: G1Affine::new(String::from("0x1"), String::from("0x2")),
+                    b: G2Affine::new(
+                        G1Affine::new(String::from("0x1"), String::from("0x2")),
+                        G1Affine::new(String::from("0x3"), String::from("0x4")),
+                    ),
+                    c: G1Affine::new(String::from("0x1"), String::from("0x2")),
+                },
+                inputs.into_iter().map(String::from).collect(),
+                String::new(),
+            )
+        };
+
+        let hex = proof(vec![
+            "0x000000000000000000000000000000000000000000000000000000000000002a",
+            "0x0000000000000000000000000000000000000000000000000000000000000001",
+        ]);
+        let short_hex = proof(vec!["0x2a", "0x1"]);
+        let decimal = proof(vec!["42", "1"]);
+
+        let hash = hex.public_input_hash::<Bn128Field>().unwrap();
+        assert_eq!(short_hex.public_input_hash::<Bn128Field>(), Ok(hash));
+        assert_eq!(decimal.public_input_hash::<Bn128Field>(), Ok(hash));
+
+        assert_ne!(
+            proof(vec!["42", "2"]).public_input_hash::<Bn128Field>(),
+            Ok(hash)
+        );
+        assert_ne!(
+            proof(vec!["1", "42"]).public_input_hash::<Bn128Field>(),
+            Ok(hash)
+        );
+        // unprefixed inputs are decimal, like when verifying
+        assert_eq!(
+            proof(vec!["10"]).public_input_hash::<Bn128Field>(),
+            proof(vec!["0xa"]).public_input_hash::<Bn128Field>()
+        );
+        assert_ne!(
+            proof(vec!["10"]).public_input_hash::<Bn128Field>(),
+            proof(vec!["0x10"]).public_input_hash::<Bn128Field>()
+        );
+        assert_eq!(
+            proof(vec!["-1"]).public_input_hash::<Bn128Field>(),
+            proof(vec![&format!("0x{:x}", Bn128Field::modulus() - 1u32)])
+                .public_input_hash::<Bn128Field>()
+        );
+
+        assert!(proof(vec!["forty-two"])
+            .public_input_hash::<Bn128Field>()
+            .is_err());
+        assert!(proof(vec![&Bn128Field::modulus().to_string()])
+            .public_input_hash::<Bn128Field>()
+            .is_err());
+    }
+
+    #[test]
+    fn verify_ref() {
+        let program = identity_program();
+
+        let keypair = G16::setup(program.clone());
+        let vk = keypair.vk;
+
+        let interpreter = Interpreter::default();
+
+        let first = G16::generate_proof(
+            program.clone(),
+            interpreter
+                .execute(&program, &vec![Bn128Field::from(1)])
+                .unwrap(),
+            keypair.pk.clone(),
+        );
+        let second = G16::generate_proof(
+            program.clone(),
+            interpreter
+                .execute(&program, &vec![Bn128Field::from(2)])
+                .unwrap(),
+            keypair.pk,
+        );
+
+        assert_eq!(G16::verify_ref::<Bn128Field>(&vk, &first), Ok(true));
+        assert_eq!(G16::verify_ref::<Bn128Field>(&vk, &second), Ok(true));
+
+        let mut invalid = second;
+        invalid.inputs = first.inputs;
+        assert_eq!(G16::verify_ref::<Bn128Field>(&vk, &invalid), Ok(false));
+    }
+
+    #[test]
+    fn verify_with_expected_inputs() {
+        let (keypair, proof) = setup_and_prove(42);
+        let vk = keypair.vk;
+
+        let verify = |proof: &Proof<ProofPoints>, expected: &[u32]| {
+            let expected: Vec<_> = expected.iter().map(|&e| Bn128Field::from(e)).collect();
+            G16::verify_with_expected_inputs::<Bn128Field>(&vk, proof, &expected)
+        };
+
+        assert_eq!(verify(&proof, &[42, 42]), Ok(true));
+        assert_eq!(
+            verify(&proof, &[42, 43]),
+            Err(Error::UnexpectedPublicInput { index: 1 })
+        );
+        assert_eq!(
+            verify(&proof, &[42]),
+            Err(Error::InputCountMismatch {
+                expected: 1,
+                found: 2
+            })
+        );
+
+        let mut invalid = proof.clone();
+        invalid.proof.a = proof.proof.c.clone();
+        assert_eq!(verify(&invalid, &[42, 42]), Ok(false));
+        assert_eq!(
+            verify(&invalid, &[43, 42]),
+            Err(Error::UnexpectedPublicInput { index: 0 })
+        );
+    }
+
+    #[test]
+    fn verify_with_partial() {
+        let program: Prog<Bn128Field> = Prog {
+            main: Function {
+                id: String::from("main"),
+                arguments: vec![FlatVariable::new(0), FlatVariable::new(1)],
+                returns: vec![FlatVariable::public(0)],
+                statements: vec![Statement::Constraint(
+                    QuadComb::from_linear_combinations(
+                        FlatVariable::new(0).into(),
+                        FlatVariable::new(1).into(),
+                    ),
+                    FlatVariable::public(0).into(),
+                )],
+            },
+            private: vec![false, false],
+        };
+
+        let keypair = G16::setup(program.clone());
+        let vk = keypair.vk;
+
+        let witness = Interpreter::default()
+            .execute(&program, &vec![Bn128Field::from(3), Bn128Field::from(4)])
+            .unwrap();
+        let proof = G16::generate_proof(program, witness, keypair.pk);
+
+        let inputs: Vec<_> = vec![3, 4, 12].into_iter().map(Bn128Field::from).collect();
+        assert_eq!(
+            G16::verify_core::<Bn128Field>(&vk, &proof.proof, &inputs),
+            Ok(true)
+        );
+
+        let partial = vk
+            .precompute_fixed_inputs(&[(2, Bn128Field::from(12)), (0, Bn128Field::from(3))])
+            .unwrap();
+        assert_eq!(partial.varying_indices(), &[1]);
+        assert_eq!(
+            G16::verify_with_partial(&partial, &proof.proof, &[Bn128Field::from(4)]),
+            Ok(true)
+        );
+
+        // the partial computation agrees with the full one on wrong inputs
+        let wrong: Vec<_> = vec![3, 5, 12].into_iter().map(Bn128Field::from).collect();
+        assert_eq!(
+            G16::verify_core::<Bn128Field>(&vk, &proof.proof, &wrong),
+            Ok(false)
+        );
+        assert_eq!(
+            G16::verify_with_partial(&partial, &proof.proof, &[Bn128Field::from(5)]),
+            Ok(false)
+        );
+        let wrongly_fixed = vk
+            .precompute_fixed_inputs(&[(0, Bn128Field::from(2))])
+            .unwrap();
+        assert_eq!(
+            G16::verify_with_partial(
+                &wrongly_fixed,
+                &proof.proof,
+                &[Bn128Field::from(4), Bn128Field::from(12)]
+            ),
+            Ok(false)
+        );
+
+        // with all or none of the inputs fixed
+        let all = vk
+            .precompute_fixed_inputs(&[
+                (0, Bn128Field::from(3)),
+                (1, Bn128Field::from(4)),
+                (2, Bn128Field::from(12)),
+            ])
+            .unwrap();
+        assert_eq!(G16::verify_with_partial(&all, &proof.proof, &[]), Ok(true));
+        let none = vk.precompute_fixed_inputs(&[]).unwrap();
+        assert_eq!(
+            G16::verify_with_partial(&none, &proof.proof, &inputs),
+            Ok(true)
+        );
+
+        assert_eq!(
+            G16::verify_with_partial(&partial, &proof.proof, &inputs),
+            Err(Error::InputCountMismatch {
+                expected: 1,
+                found: 3
+            })
+        );
+    }
+
+    #[test]
+    fn precompute_fixed_inputs_invalid() {
+        let vk = G16::setup(identity_program()).vk;
+
+        assert_eq!(
+            vk.precompute_fixed_inputs(&[(0, Bn128Field::from(1)), (0, Bn128Field::from(1))])
+                .unwrap_err(),
+            Error::DuplicateFixedInput { index: 0 }
+        );
+        assert_eq!(
+            vk.precompute_fixed_inputs(&[(2, Bn128Field::from(1))])
+                .unwrap_err(),
+            Error::InputCountMismatch {
+                expected: 2,
+                found: 3
+            }
+        );
+
+        let mut malformed = vk;
+        malformed.alpha = G1Affine::new(String::from("0x1"), String::from("0x1"));
+        match malformed.precompute_fixed_inputs(&[(0, Bn128Field::from(1))]) {
+            Err(Error::InvalidPoint { .. }) => {}
+            r => panic!("unexpected result {:?}", r),
+        }
+    }
+
+    #[test]
+    fn verify_all() {
+        let program = identity_program();
+
+        let keypair = G16::setup(program.clone());
+
+        let interpreter = Interpreter::default();
+
+        let proofs: Vec<_> = vec![1, 2, 3]
+            .into_iter()
+            .map(|i| {
+                let witness = interpreter
+                    .execute(&program, &vec![Bn128Field::from(i)])
+                    .unwrap();
+                G16::generate_proof(program.clone(), witness, keypair.pk.clone())
+            })
+            .collect();
+
+        let pairs: Vec<_> = proofs.iter().map(|proof| (&keypair.vk, proof)).collect();
+        assert_eq!(G16::verify_all::<Bn128Field>(&pairs), Ok(true));
+
+        // the second proof claims other public inputs
+        let mut invalid = proofs[1].clone();
+        invalid.inputs = proofs[0].inputs.clone();
+
+        let pairs = vec![
+            (&keypair.vk, &proofs[0]),
+            (&keypair.vk, &invalid),
+            (&keypair.vk, &proofs[2]),
+        ];
+        assert_eq!(
+            G16::verify_all::<Bn128Field>(&pairs),
+            Err((1, Error::VerificationFailed))
+        );
+    }
+}
@@ -1,4 +1,5 @@
 pub mod groth16;
+mod r1cs;
 
 extern crate rand;
 
@@ -10,13 +11,14 @@ use bellman::groth16::{
 };
 use bellman::pairing::ff::ScalarEngine;
 use bellman::{Circuit, ConstraintSystem, LinearCombination, SynthesisError, Variable};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use zokrates_field::Field;
 
-use self::rand::ChaChaRng;
+use self::rand::{ChaChaRng, Rng};
 use crate::flat_absy::FlatVariable;
 
 pub use self::parse::*;
+pub use self::r1cs::R1cs;
 
 #[derive(Clone)]
 pub struct Computation<T> {
@@ -32,6 +34,37 @@ impl<T: Field> Computation<T> {
         }
     }
 
+    /// Builds the witness of `program` from the values of its variables. Every argument of the
+    /// program and every variable of its constraints needs a value, the first one without a value
+    /// is returned as an error. Values of other variables, and of the constant `~one`, are ignored.
+    pub fn with_witness_map(
+        program: Prog<T>,
+        values: HashMap<FlatVariable, T>,
+    ) -> Result<Self, FlatVariable> {
+        let constraint_variables = program.main.statements.iter().flat_map(|s| match s {
+            Statement::Constraint(quad, lin) => vec![&quad.left, &quad.right, lin]
+                .into_iter()
+                .flat_map(|l| l.0.iter().map(|(v, _)| *v))
+                .collect(),
+            Statement::Directive(..) => vec![],
+        });
+
+        let mut witness = BTreeMap::new();
+        witness.insert(FlatVariable::one(), T::one());
+        for v in program
+            .main
+            .arguments
+            .iter()
+            .cloned()
+            .chain(constraint_variables)
+            .filter(|v| *v != FlatVariable::one())
+        {
+            witness.insert(v, values.get(&v).cloned().ok_or(v)?);
+        }
+
+        Ok(Computation::with_witness(program, Witness(witness)))
+    }
+
     pub fn without_witness(program: Prog<T>) -> Self {
         Computation {
             program,
@@ -164,6 +197,17 @@ impl<T: Field> Computation<T> {
     pub fn prove(self, params: &Parameters<T::BellmanEngine>) -> Proof<T::BellmanEngine> {
         let rng = &mut ChaChaRng::new_unseeded();
 
+        self.prove_with_rng(params, rng)
+    }
+
+    /// Generates a proof, drawing its randomness from `rng`. The same randomness yields the same proof,
+    /// which makes proofs reproducible in tests. Outside of tests, `rng` must be a cryptographically
+    /// secure generator which is seeded unpredictably.
+    pub fn prove_with_rng<R: Rng>(
+        self,
+        params: &Parameters<T::BellmanEngine>,
+        rng: &mut R,
+    ) -> Proof<T::BellmanEngine> {
         let proof = create_random_proof(self.clone(), params, rng).unwrap();
 
         let pvk = prepare_verifying_key(&params.vk);
@@ -177,17 +221,13 @@ impl<T: Field> Computation<T> {
     }
 
     pub fn public_inputs_values(&self) -> Vec<<T::BellmanEngine as ScalarEngine>::Fr> {
+        let witness = self.witness.as_ref().unwrap();
+
         self.program
-            .main
-            .arguments
-            .clone()
+            .public_input_layout()
+            .variables()
             .iter()
-            .zip(self.program.private.clone())
-            .filter(|(_, p)| !p)
-            .map(|(a, _)| a)
-            .map(|v| self.witness.clone().unwrap().0.get(v).unwrap().clone())
-            .chain(self.witness.clone().unwrap().return_values())
-            .map(|v| v.clone().into_bellman())
+            .map(|v| witness.0.get(v).unwrap().clone().into_bellman())
             .collect()
     }
 
@@ -321,6 +361,53 @@ mod tests {
             let _proof = computation.prove(&params);
         }
 
+        #[test]
+        fn with_witness_map() {
+            let program: Prog<Bn128Field> = Prog {
+                main: Function {
+                    id: String::from("main"),
+                    arguments: vec![FlatVariable::new(0)],
+                    returns: vec![FlatVariable::public(0)],
+                    statements: vec![Statement::Constraint(
+                        FlatVariable::new(0).into(),
+                        FlatVariable::public(0).into(),
+                    )],
+                },
+                private: vec![false],
+            };
+
+            let mut values = HashMap::new();
+            values.insert(FlatVariable::new(0), Bn128Field::from(42));
+
+            assert_eq!(
+                Computation::with_witness_map(program.clone(), values.clone())
+                    .err()
+                    .unwrap(),
+                FlatVariable::public(0)
+            );
+
+            values.insert(FlatVariable::public(0), Bn128Field::from(42));
+            // values of variables which are not part of the program are ignored
+            values.insert(FlatVariable::new(1), Bn128Field::from(1));
+
+            let computation = Computation::with_witness_map(program.clone(), values).unwrap();
+
+            let witness = Interpreter::default()
+                .execute(&program, &vec![Bn128Field::from(42)])
+                .unwrap();
+            assert_eq!(computation.witness, Some(witness));
+
+            let params = computation.clone().setup();
+            let proof = computation.clone().prove(&params);
+
+            assert!(verify_proof(
+                &prepare_verifying_key(&params.vk),
+                &proof,
+                &computation.public_inputs_values()
+            )
+            .unwrap());
+        }
+
         #[test]
         fn public_identity() {
             let program: Prog<Bn128Field> = Prog {
@@ -465,4 +552,30 @@ mod tests {
             let _proof = computation.prove(&params);
         }
     }
+
+    mod parse {
+        use super::*;
+        use pairing::bn256::{Bn256, Fq};
+        use pairing::{to_hex, CurveAffine, Engine};
+        use proof_system::{G1Affine, G2Affine};
+
+        #[test]
+        fn g2_math_order() {
+            let g2 = <Bn256 as Engine>::G2Affine::one();
+            let parsed = parse_g2::<Bn128Field>(&g2);
+
+            assert_eq!(parsed.in_math_order().in_math_order(), parsed);
+            assert_eq!(G2Affine::from_math_order(&parsed.in_math_order()), parsed);
+
+            let (x, y) = g2.into_xy_unchecked();
+            let hex = |e: &Fq| format!("0x{}", to_hex(e));
+            assert_eq!(
+                parsed.in_math_order(),
+                G2Affine::new(
+                    G1Affine::new(hex(&x.c0), hex(&x.c1)),
+                    G1Affine::new(hex(&y.c0), hex(&y.c1))
+                )
+            );
+        }
+    }
 }
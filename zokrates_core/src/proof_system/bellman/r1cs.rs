@@ -0,0 +1,532 @@
+use bellman::pairing::ff::ScalarEngine;
+use bellman::{ConstraintSystem, Index, LinearCombination, SynthesisError, Variable};
+use num_bigint::BigUint;
+use std::io::{self, Write};
+use zokrates_field::Field;
+
+use super::Computation;
+use crate::ir::{Prog, Witness};
+
+/// The constraints of a program as sparse matrices. Row `i` of `a`, `b` and `c` holds the
+/// `(wire, coefficient)` pairs of the linear combinations of constraint `i`, so that an assignment
+/// `w` satisfies the system if `<a_i, w> * <b_i, w> = <c_i, w>` for all `i`.
+///
+/// Wire `0` is the constant `1`, followed by the `num_inputs - 1` public wires, followed by the
+/// `num_aux` private wires, in the order in which they are allocated during synthesis.
+#[derive(Debug, Clone, PartialEq)]
+pub struct R1cs<T> {
+    pub num_inputs: usize,
+    pub num_aux: usize,
+    pub a: Vec<Vec<(usize, T)>>,
+    pub b: Vec<Vec<(usize, T)>>,
+    pub c: Vec<Vec<(usize, T)>>,
+}
+
+impl<T> R1cs<T> {
+    pub fn num_constraints(&self) -> usize {
+        self.a.len()
+    }
+
+    pub fn num_wires(&self) -> usize {
+        self.num_inputs + self.num_aux
+    }
+}
+
+impl<T: Field> Computation<T> {
+    /// Synthesizes the program like the setup does, collecting the constraints instead of
+    /// evaluating them
+    pub fn to_r1cs(&self) -> R1cs<T> {
+        let mut assembly = R1csAssembly {
+            num_inputs: 1,
+            num_aux: 0,
+            constraints: vec![],
+        };
+
+        self.program
+            .clone()
+            .synthesize(&mut assembly, None)
+            .unwrap();
+
+        let num_inputs = assembly.num_inputs;
+        let wire = |index: Index| match index {
+            Index::Input(i) => i,
+            Index::Aux(i) => num_inputs + i,
+        };
+        let row = |lc: Row<T>| {
+            lc.into_iter()
+                .map(|(index, coefficient)| (wire(index), coefficient))
+                .collect()
+        };
+
+        let mut r1cs = R1cs {
+            num_inputs,
+            num_aux: assembly.num_aux,
+            a: vec![],
+            b: vec![],
+            c: vec![],
+        };
+
+        for (a, b, c) in assembly.constraints {
+            r1cs.a.push(row(a));
+            r1cs.b.push(row(b));
+            r1cs.c.push(row(c));
+        }
+
+        r1cs
+    }
+}
+
+type Row<T> = Vec<(Index, T)>;
+
+const R1CS_MAGIC: &[u8; 4] = b"r1cs";
+const R1CS_VERSION: u32 = 1;
+const R1CS_HEADER_SECTION: u32 = 1;
+const R1CS_CONSTRAINTS_SECTION: u32 = 2;
+const R1CS_WIRE_TO_LABEL_SECTION: u32 = 3;
+
+const WTNS_MAGIC: &[u8; 4] = b"wtns";
+const WTNS_VERSION: u32 = 2;
+const WTNS_HEADER_SECTION: u32 = 1;
+const WTNS_VALUES_SECTION: u32 = 2;
+
+/// The number of bytes of a field element in the binary formats of circom, a whole number of 64 bit
+/// words
+fn field_size<T: Field>() -> usize {
+    ((T::modulus().bits() + 63) / 64 * 8) as usize
+}
+
+fn write_sections<W: Write>(
+    mut w: W,
+    magic: &[u8; 4],
+    version: u32,
+    sections: &[(u32, Vec<u8>)],
+) -> io::Result<()> {
+    w.write_all(magic)?;
+    w.write_all(&version.to_le_bytes())?;
+    w.write_all(&(sections.len() as u32).to_le_bytes())?;
+    for (section_type, section) in sections {
+        w.write_all(&section_type.to_le_bytes())?;
+        w.write_all(&(section.len() as u64).to_le_bytes())?;
+        w.write_all(section)?;
+    }
+
+    Ok(())
+}
+
+impl<T: Field> Computation<T> {
+    /// Writes the constraints of the program in the binary `.r1cs` format used by circom and snarkjs.
+    ///
+    /// The public wires of ZoKrates are not split into inputs and outputs, so they are all declared
+    /// as public inputs. Private inputs are the private arguments of the program, and the label of
+    /// each wire is its index.
+    pub fn write_r1cs<W: Write>(&self, w: W) -> io::Result<()> {
+        let r1cs = self.to_r1cs();
+
+        let modulus = T::modulus();
+        let n8 = field_size::<T>();
+
+        let mut header = vec![];
+        header.write_all(&(n8 as u32).to_le_bytes())?;
+        header.write_all(&to_le_bytes(&modulus, n8))?;
+        header.write_all(&(r1cs.num_wires() as u32).to_le_bytes())?;
+        header.write_all(&0u32.to_le_bytes())?;
+        header.write_all(&(r1cs.num_inputs as u32 - 1).to_le_bytes())?;
+        header.write_all(&(self.program.private_input_count() as u32).to_le_bytes())?;
+        header.write_all(&(r1cs.num_wires() as u64).to_le_bytes())?;
+        header.write_all(&(r1cs.num_constraints() as u32).to_le_bytes())?;
+
+        let mut constraints = vec![];
+        for row in r1cs
+            .a
+            .iter()
+            .zip(r1cs.b.iter())
+            .zip(r1cs.c.iter())
+            .flat_map(|((a, b), c)| vec![a, b, c])
+        {
+            constraints.write_all(&(row.len() as u32).to_le_bytes())?;
+            for (wire, coefficient) in row {
+                constraints.write_all(&(*wire as u32).to_le_bytes())?;
+                constraints.write_all(&to_le_bytes(&coefficient.to_biguint(), n8))?;
+            }
+        }
+
+        let mut labels = vec![];
+        for wire in 0..r1cs.num_wires() {
+            labels.write_all(&(wire as u64).to_le_bytes())?;
+        }
+
+        write_sections(
+            w,
+            R1CS_MAGIC,
+            R1CS_VERSION,
+            &[
+                (R1CS_HEADER_SECTION, header),
+                (R1CS_CONSTRAINTS_SECTION, constraints),
+                (R1CS_WIRE_TO_LABEL_SECTION, labels),
+            ],
+        )
+    }
+}
+
+impl<T: Field> Witness<T> {
+    /// Writes the witness in the binary `.wtns` format used by snarkjs, so that it can be used to
+    /// prove the constraints written by `Computation::write_r1cs` for `program`. The values are
+    /// ordered like the wires of these constraints, which depends on the program.
+    pub fn write_wtns<W: Write>(&self, program: &Prog<T>, w: W) -> io::Result<()> {
+        let mut assembly = WtnsAssembly {
+            inputs: vec![T::one()],
+            aux: vec![],
+        };
+
+        program
+            .clone()
+            .synthesize(&mut assembly, Some(self.clone()))
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        let n8 = field_size::<T>();
+        let wires = assembly.inputs.len() + assembly.aux.len();
+
+        let mut header = vec![];
+        header.write_all(&(n8 as u32).to_le_bytes())?;
+        header.write_all(&to_le_bytes(&T::modulus(), n8))?;
+        header.write_all(&(wires as u32).to_le_bytes())?;
+
+        let mut values = vec![];
+        for value in assembly.inputs.iter().chain(assembly.aux.iter()) {
+            values.write_all(&to_le_bytes(&value.to_biguint(), n8))?;
+        }
+
+        write_sections(
+            w,
+            WTNS_MAGIC,
+            WTNS_VERSION,
+            &[(WTNS_HEADER_SECTION, header), (WTNS_VALUES_SECTION, values)],
+        )
+    }
+}
+
+fn to_le_bytes(value: &BigUint, len: usize) -> Vec<u8> {
+    let mut bytes = value.to_bytes_le();
+    bytes.resize(len, 0);
+    bytes
+}
+
+/// A constraint system which records the constraints, with the wire indices as allocated by bellman
+struct R1csAssembly<T> {
+    num_inputs: usize,
+    num_aux: usize,
+    constraints: Vec<(Row<T>, Row<T>, Row<T>)>,
+}
+
+fn collect<T: Field>(lc: &LinearCombination<T::BellmanEngine>) -> Row<T> {
+    lc.as_ref()
+        .iter()
+        .map(|(variable, coefficient)| (variable.get_unchecked(), T::from_bellman(*coefficient)))
+        .collect()
+}
+
+/// A constraint system which records the values of the wires, in the order in which they are
+/// allocated during synthesis
+struct WtnsAssembly<T> {
+    inputs: Vec<T>,
+    aux: Vec<T>,
+}
+
+impl<T: Field> ConstraintSystem<T::BellmanEngine> for WtnsAssembly<T> {
+    type Root = Self;
+
+    fn alloc<F, A, AR>(&mut self, _: A, f: F) -> Result<Variable, SynthesisError>
+    where
+        F: FnOnce() -> Result<<T::BellmanEngine as ScalarEngine>::Fr, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.aux.push(T::from_bellman(f()?));
+
+        Ok(Variable::new_unchecked(Index::Aux(self.aux.len() - 1)))
+    }
+
+    fn alloc_input<F, A, AR>(&mut self, _: A, f: F) -> Result<Variable, SynthesisError>
+    where
+        F: FnOnce() -> Result<<T::BellmanEngine as ScalarEngine>::Fr, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.inputs.push(T::from_bellman(f()?));
+
+        Ok(Variable::new_unchecked(Index::Input(self.inputs.len() - 1)))
+    }
+
+    fn enforce<A, AR, LA, LB, LC>(&mut self, _: A, _: LA, _: LB, _: LC)
+    where
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+        LA: FnOnce(LinearCombination<T::BellmanEngine>) -> LinearCombination<T::BellmanEngine>,
+        LB: FnOnce(LinearCombination<T::BellmanEngine>) -> LinearCombination<T::BellmanEngine>,
+        LC: FnOnce(LinearCombination<T::BellmanEngine>) -> LinearCombination<T::BellmanEngine>,
+    {
+    }
+
+    fn push_namespace<NR, N>(&mut self, _: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+    }
+
+    fn pop_namespace(&mut self) {}
+
+    fn get_root(&mut self) -> &mut Self::Root {
+        self
+    }
+}
+
+impl<T: Field> ConstraintSystem<T::BellmanEngine> for R1csAssembly<T> {
+    type Root = Self;
+
+    fn alloc<F, A, AR>(&mut self, _: A, _: F) -> Result<Variable, SynthesisError>
+    where
+        F: FnOnce() -> Result<<T::BellmanEngine as ScalarEngine>::Fr, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        // the values of the wires are not needed to collect the constraints
+        let index = self.num_aux;
+        self.num_aux += 1;
+
+        Ok(Variable::new_unchecked(Index::Aux(index)))
+    }
+
+    fn alloc_input<F, A, AR>(&mut self, _: A, _: F) -> Result<Variable, SynthesisError>
+    where
+        F: FnOnce() -> Result<<T::BellmanEngine as ScalarEngine>::Fr, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        let index = self.num_inputs;
+        self.num_inputs += 1;
+
+        Ok(Variable::new_unchecked(Index::Input(index)))
+    }
+
+    fn enforce<A, AR, LA, LB, LC>(&mut self, _: A, a: LA, b: LB, c: LC)
+    where
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+        LA: FnOnce(LinearCombination<T::BellmanEngine>) -> LinearCombination<T::BellmanEngine>,
+        LB: FnOnce(LinearCombination<T::BellmanEngine>) -> LinearCombination<T::BellmanEngine>,
+        LC: FnOnce(LinearCombination<T::BellmanEngine>) -> LinearCombination<T::BellmanEngine>,
+    {
+        let a = a(LinearCombination::zero());
+        let b = b(LinearCombination::zero());
+        let c = c(LinearCombination::zero());
+
+        self.constraints
+            .push((collect(&a), collect(&b), collect(&c)));
+    }
+
+    fn push_namespace<NR, N>(&mut self, _: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+    }
+
+    fn pop_namespace(&mut self) {}
+
+    fn get_root(&mut self) -> &mut Self::Root {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::flat_absy::FlatVariable;
+    use crate::ir::{Function, Interpreter, LinComb, Prog, QuadComb, Statement};
+    use crate::proof_system::identity_program;
+    use std::convert::TryInto;
+    use zokrates_field::Bn128Field;
+
+    #[test]
+    fn identity() {
+        let r1cs = Computation::without_witness(identity_program()).to_r1cs();
+
+        // ~one * _0 == ~out_0, with _0 and ~out_0 on public wires 1 and 2
+        assert_eq!(
+            r1cs,
+            R1cs {
+                num_inputs: 3,
+                num_aux: 0,
+                a: vec![vec![(0, Bn128Field::from(1))]],
+                b: vec![vec![(1, Bn128Field::from(1))]],
+                c: vec![vec![(2, Bn128Field::from(1))]],
+            }
+        );
+        assert_eq!(r1cs.num_constraints(), 1);
+        assert_eq!(r1cs.num_wires(), 3);
+    }
+
+    #[test]
+    fn private_wires() {
+        let program: Prog<Bn128Field> = Prog {
+            main: Function {
+                id: String::from("main"),
+                arguments: vec![FlatVariable::new(0)],
+                returns: vec![FlatVariable::public(0)],
+                statements: vec![
+                    Statement::Constraint(
+                        QuadComb::from_linear_combinations(
+                            FlatVariable::new(0).into(),
+                            FlatVariable::new(0).into(),
+                        ),
+                        FlatVariable::new(1).into(),
+                    ),
+                    Statement::Constraint(
+                        (LinComb::from(FlatVariable::new(1)) * &Bn128Field::from(3)).into(),
+                        FlatVariable::public(0).into(),
+                    ),
+                ],
+            },
+            private: vec![true],
+        };
+
+        let r1cs = Computation::without_witness(program).to_r1cs();
+
+        // _0 * _0 == _1, 3 * _1 == ~out_0 with the private _0 and _1 on wires 2 and 3
+        assert_eq!(
+            r1cs,
+            R1cs {
+                num_inputs: 2,
+                num_aux: 2,
+                a: vec![
+                    vec![(2, Bn128Field::from(1))],
+                    vec![(0, Bn128Field::from(1))]
+                ],
+                b: vec![
+                    vec![(2, Bn128Field::from(1))],
+                    vec![(3, Bn128Field::from(3))]
+                ],
+                c: vec![
+                    vec![(3, Bn128Field::from(1))],
+                    vec![(1, Bn128Field::from(1))]
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn write_r1cs() {
+        let mut buffer = vec![];
+        Computation::without_witness(identity_program())
+            .write_r1cs(&mut buffer)
+            .unwrap();
+
+        let u32_at = |i: usize| u32::from_le_bytes(buffer[i..i + 4].try_into().unwrap());
+        let u64_at = |i: usize| u64::from_le_bytes(buffer[i..i + 8].try_into().unwrap());
+
+        assert_eq!(&buffer[0..4], b"r1cs");
+        assert_eq!(u32_at(4), 1);
+        assert_eq!(u32_at(8), 3);
+
+        // header section
+        assert_eq!(u32_at(12), 1);
+        let header_size = u64_at(16) as usize;
+        let header = 24;
+        assert_eq!(u32_at(header), 32);
+        assert_eq!(
+            BigUint::from_bytes_le(&buffer[header + 4..header + 36]),
+            Bn128Field::modulus()
+        );
+        assert_eq!(u32_at(header + 36), 3); // wires
+        assert_eq!(u32_at(header + 40), 0); // public outputs
+        assert_eq!(u32_at(header + 44), 2); // public inputs
+        assert_eq!(u32_at(header + 48), 0); // private inputs
+        assert_eq!(u64_at(header + 52), 3); // labels
+        assert_eq!(u32_at(header + 60), 1); // constraints
+        assert_eq!(header_size, 64);
+
+        // constraints section, holding a single constraint with one factor per linear combination
+        let constraints = header + header_size;
+        assert_eq!(u32_at(constraints), 2);
+        assert_eq!(u64_at(constraints + 4), 3 * (4 + 4 + 32));
+        assert_eq!(u32_at(constraints + 12), 1);
+        assert_eq!(u32_at(constraints + 16), 0);
+        assert_eq!(buffer[constraints + 20], 1);
+
+        // wire to label section
+        let labels = constraints + 12 + 3 * (4 + 4 + 32);
+        assert_eq!(u32_at(labels), 3);
+        assert_eq!(u64_at(labels + 4), 3 * 8);
+        assert_eq!(buffer.len(), labels + 12 + 3 * 8);
+    }
+
+    #[test]
+    fn write_wtns() {
+        let program: Prog<Bn128Field> = Prog {
+            main: Function {
+                id: String::from("main"),
+                arguments: vec![FlatVariable::new(0)],
+                returns: vec![FlatVariable::public(0)],
+                statements: vec![
+                    Statement::Constraint(
+                        QuadComb::from_linear_combinations(
+                            FlatVariable::new(0).into(),
+                            FlatVariable::new(0).into(),
+                        ),
+                        FlatVariable::new(1).into(),
+                    ),
+                    Statement::Constraint(
+                        (LinComb::from(FlatVariable::new(1)) * &Bn128Field::from(3)).into(),
+                        FlatVariable::public(0).into(),
+                    ),
+                ],
+            },
+            private: vec![true],
+        };
+
+        let witness = Interpreter::default()
+            .execute(&program, &vec![Bn128Field::from(5)])
+            .unwrap();
+
+        let mut buffer = vec![];
+        witness.write_wtns(&program, &mut buffer).unwrap();
+
+        let u32_at = |i: usize| u32::from_le_bytes(buffer[i..i + 4].try_into().unwrap());
+        let u64_at = |i: usize| u64::from_le_bytes(buffer[i..i + 8].try_into().unwrap());
+
+        assert_eq!(&buffer[0..4], b"wtns");
+        assert_eq!(u32_at(4), 2);
+        assert_eq!(u32_at(8), 2);
+
+        // header section
+        assert_eq!(u32_at(12), 1);
+        assert_eq!(u64_at(16), 40);
+        let header = 24;
+        assert_eq!(u32_at(header), 32);
+        assert_eq!(
+            BigUint::from_bytes_le(&buffer[header + 4..header + 36]),
+            Bn128Field::modulus()
+        );
+        assert_eq!(u32_at(header + 36), 4);
+
+        // values section, in the order of the wires of the r1cs: ~one, ~out_0, _0, _1
+        let values = header + 40;
+        assert_eq!(u32_at(values), 2);
+        assert_eq!(u64_at(values + 4), 4 * 32);
+        let value = |i: usize| {
+            BigUint::from_bytes_le(&buffer[values + 12 + i * 32..values + 12 + (i + 1) * 32])
+        };
+        assert_eq!(value(0), BigUint::from(1u32));
+        assert_eq!(value(1), BigUint::from(75u32));
+        assert_eq!(value(2), BigUint::from(5u32));
+        assert_eq!(value(3), BigUint::from(25u32));
+        assert_eq!(buffer.len(), values + 12 + 4 * 32);
+
+        // the number of wires matches the r1cs of the program
+        assert_eq!(
+            Computation::without_witness(program).to_r1cs().num_wires(),
+            4
+        );
+    }
+}
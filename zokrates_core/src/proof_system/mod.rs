@@ -2,12 +2,15 @@ pub mod bellman;
 #[cfg(feature = "libsnark")]
 pub mod libsnark;
 
-mod solidity;
+pub mod solidity;
 
 use crate::ir;
-use serde::de::DeserializeOwned;
-use serde::Serialize;
-use zokrates_field::Field;
+use num_bigint::BigUint;
+use serde::de::{DeserializeOwned, Error as _};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sha3::{Digest, Keccak256};
+use std::io::{Read, Write};
+use zokrates_field::{Bn128Field, Field};
 
 // We only need to serialize this struct, there is no need for deserialization as keys are
 // used separately in other use cases
@@ -23,6 +26,171 @@ impl<V: Serialize + DeserializeOwned> SetupKeypair<V> {
     }
 }
 
+const SETUP_BUNDLE_MAGIC: &[u8; 4] = b"ZKSB";
+const SETUP_BUNDLE_VERSION_1: &[u8; 4] = &[0, 0, 0, 1];
+
+/// A setup together with the hash of the program it was run for, so that a deployment can be
+/// audited from the program to the keys
+#[derive(Debug, Clone, PartialEq)]
+pub struct SetupBundle<V> {
+    /// the keccak256 hash of the program as written by `Prog::serialize`, see `program_hash`
+    pub program_hash: [u8; 32],
+    pub vk: V,
+    pub pk: Vec<u8>,
+}
+
+impl<V: Serialize + DeserializeOwned> SetupBundle<V> {
+    pub fn new<T: Field>(program: &ir::Prog<T>, keypair: SetupKeypair<V>) -> Self {
+        SetupBundle {
+            program_hash: program_hash(program),
+            vk: keypair.vk,
+            pk: keypair.pk,
+        }
+    }
+
+    /// Writes the bundle as the magic number `ZKSB` and the version `1` in 4 bytes each, the
+    /// program hash, then the verification key as JSON and the proving key, each preceded by its
+    /// length in bytes as a big endian `u64`
+    pub fn write<W: Write>(&self, mut w: W) -> std::io::Result<()> {
+        let vk = serde_json::to_vec(&self.vk).unwrap();
+
+        w.write_all(SETUP_BUNDLE_MAGIC)?;
+        w.write_all(SETUP_BUNDLE_VERSION_1)?;
+        w.write_all(&self.program_hash)?;
+        w.write_all(&(vk.len() as u64).to_be_bytes())?;
+        w.write_all(&vk)?;
+        w.write_all(&(self.pk.len() as u64).to_be_bytes())?;
+        w.write_all(&self.pk)
+    }
+
+    /// Reads a bundle written by `write`. The program hash is not checked, see `check_program`.
+    pub fn read<R: Read>(mut r: R) -> Result<Self, String> {
+        let mut magic = [0; 4];
+        r.read_exact(&mut magic)
+            .map_err(|_| String::from("Cannot read magic number"))?;
+        if &magic != SETUP_BUNDLE_MAGIC {
+            return Err(String::from("Wrong magic number"));
+        }
+
+        let mut version = [0; 4];
+        r.read_exact(&mut version)
+            .map_err(|_| String::from("Cannot read version"))?;
+        if &version != SETUP_BUNDLE_VERSION_1 {
+            return Err(String::from("Unknown version"));
+        }
+
+        let mut program_hash = [0; 32];
+        r.read_exact(&mut program_hash)
+            .map_err(|_| String::from("Cannot read program hash"))?;
+
+        let vk = read_section(&mut r, "verification key")?;
+        let vk = serde_json::from_slice(&vk)
+            .map_err(|e| format!("Cannot read verification key: {}", e))?;
+
+        let pk = read_section(&mut r, "proving key")?;
+
+        Ok(SetupBundle {
+            program_hash,
+            vk,
+            pk,
+        })
+    }
+
+    /// Checks that the bundle was created for `program`
+    pub fn check_program<T: Field>(&self, program: &ir::Prog<T>) -> Result<(), String> {
+        if program_hash(program) != self.program_hash {
+            return Err(format!(
+                "Setup bundle was created for the program with hash 0x{}, not for this program",
+                hex::encode(self.program_hash)
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Reads a section of a setup bundle, preceded by its length as a big endian `u64`
+fn read_section<R: Read>(r: &mut R, name: &str) -> Result<Vec<u8>, String> {
+    let mut len = [0; 8];
+    r.read_exact(&mut len)
+        .map_err(|_| format!("Cannot read length of {}", name))?;
+    let len = u64::from_be_bytes(len);
+
+    let mut section = vec![];
+    r.take(len)
+        .read_to_end(&mut section)
+        .map_err(|e| format!("Cannot read {}: {}", name, e))?;
+    if section.len() as u64 != len {
+        return Err(format!("Unexpected end of {}", name));
+    }
+
+    Ok(section)
+}
+
+/// The keccak256 hash of `program` as written by `Prog::serialize`, i.e. of its `.out` file
+pub fn program_hash<T: Field>(program: &ir::Prog<T>) -> [u8; 32] {
+    let mut serialized = vec![];
+    program.serialize(&mut serialized);
+
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&Keccak256::digest(&serialized));
+    hash
+}
+
+/// The program `def main(field x) -> field: return x` with a public argument, so that its proofs
+/// have the public inputs `[x, x]`
+pub(crate) fn identity_program() -> ir::Prog<Bn128Field> {
+    use crate::flat_absy::FlatVariable;
+    use crate::ir::{Function, Prog, Statement};
+
+    Prog {
+        main: Function {
+            id: String::from("main"),
+            arguments: vec![FlatVariable::new(0)],
+            returns: vec![FlatVariable::public(0)],
+            statements: vec![Statement::Constraint(
+                FlatVariable::new(0).into(),
+                FlatVariable::public(0).into(),
+            )],
+        },
+        private: vec![false],
+    }
+}
+
+/// Runs a setup, generates a proof and verifies it for a tiny fixed program, checking that the
+/// proof verifies for its public inputs and not for others. This takes a fraction of a second and
+/// can be used at startup to detect a broken build of the proving stack early.
+pub fn self_test() -> Result<(), String> {
+    use crate::ir::Interpreter;
+    use crate::proof_system::bellman::groth16::G16;
+
+    let program = identity_program();
+
+    let acknowledged = G16::acknowledge_malleability();
+    let keypair = G16::setup_acknowledged(program.clone(), acknowledged);
+
+    let witness = Interpreter::default()
+        .execute(&program, &vec![Bn128Field::from(42)])
+        .map_err(|e| format!("Self test failed to compute the witness: {}", e))?;
+    let mut proof = G16::generate_proof_acknowledged(program, witness, keypair.pk, acknowledged);
+
+    match G16::verify_ref::<Bn128Field>(&keypair.vk, &proof) {
+        Ok(true) => {}
+        Ok(false) => return Err(String::from("Self test proof does not verify")),
+        Err(e) => return Err(format!("Self test failed to verify the proof: {}", e)),
+    }
+
+    proof.inputs = PublicInputs(vec![Bn128Field::from(42), Bn128Field::from(43)]).to_strings();
+    match G16::verify_ref::<Bn128Field>(&keypair.vk, &proof) {
+        Ok(false) => Ok(()),
+        Ok(true) => Err(String::from(
+            "Self test proof verifies for wrong public inputs",
+        )),
+        Err(e) => Err(format!("Self test failed to verify the proof: {}", e)),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum SolidityAbi {
     V1,
     V2,
@@ -38,25 +206,292 @@ impl SolidityAbi {
     }
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Proof<T> {
     pub proof: T,
+    /// the public inputs as `0x`-prefixed hex strings. Inputs written by other tools may also be
+    /// decimal or negative, see `parse_field_input`. When deserializing, an input may also be given
+    /// as an array of limbs, see `InputEncoding`, which is converted to a hex string
+    #[serde(deserialize_with = "deserialize_inputs")]
     pub inputs: Vec<String>,
     pub raw: String,
+    /// the version of ZoKrates which generated the proof, see `check_producer_version`. Proofs
+    /// written before the version was recorded have none.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub producer_version: Option<String>,
+}
+
+/// Proofs are equal if their points, inputs and `raw` encodings are, whichever version of ZoKrates
+/// generated them
+impl<T: PartialEq> PartialEq for Proof<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.proof == other.proof && self.inputs == other.inputs && self.raw == other.raw
+    }
+}
+
+/// The encodings of a public input accepted when deserializing a `Proof`: a single hex string, or
+/// an array of 64-bit limbs, least significant first, as written by tools which keep field elements
+/// as arrays of machine words. A limb is a number, or a decimal or `0x`-prefixed hex string.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum InputEncoding {
+    Flat(String),
+    Limbs(Vec<Limb>),
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Limb {
+    Number(u64),
+    String(String),
+}
+
+impl Limb {
+    fn value(&self) -> Result<u64, String> {
+        match self {
+            Limb::Number(n) => Ok(*n),
+            Limb::String(s) => match s.strip_prefix("0x") {
+                Some(hex) => u64::from_str_radix(hex, 16),
+                None => s.parse(),
+            }
+            .map_err(|_| format!("invalid 64-bit limb `{}`", s)),
+        }
+    }
+}
+
+/// Returns the `0x`-prefixed, zero-padded hex string of the value of `limbs`
+fn limbs_to_hex(limbs: &[Limb]) -> Result<String, String> {
+    let mut value = BigUint::from(0u32);
+    for limb in limbs.iter().rev() {
+        value = (value << 64) + limb.value()?;
+    }
+    Ok(format!("0x{:0>64}", value.to_str_radix(16)))
+}
+
+/// Parses a public input. This is the only parser of public inputs, used both to read them and
+/// to verify proofs, so that a string always denotes the same value.
+///
+/// A string prefixed with `0x` is read as hex, any other string as decimal. A leading minus
+/// negates the value in the field, with the rest read by the same rule, so that `-10` is the
+/// modulus minus ten. Values which are not smaller than the modulus of `T` are rejected. On failure,
+/// returns the reason why `s` is not a valid input.
+pub(crate) fn parse_field_input<T: Field>(s: &str) -> Result<T, String> {
+    let (negative, magnitude) = match s.strip_prefix('-') {
+        Some(magnitude) => (true, magnitude),
+        None => (false, s),
+    };
+
+    let value = match magnitude.strip_prefix("0x") {
+        Some(hex) => BigUint::parse_bytes(hex.as_bytes(), 16)
+            .ok_or_else(|| format!("not a hex encoded {} value", T::name())),
+        None => BigUint::parse_bytes(magnitude.as_bytes(), 10)
+            .ok_or_else(|| format!("not a decimal encoded {} value", T::name())),
+    }?;
+
+    if value >= T::modulus() {
+        return Err(format!("not smaller than the {} modulus", T::name()));
+    }
+
+    match negative {
+        true => Ok(T::from((T::modulus() - value) % T::modulus())),
+        false => Ok(T::from(value)),
+    }
+}
+
+/// The public inputs of a proof as field elements.
+///
+/// They serialize to the canonical encoding of `Proof::inputs`: `0x`-prefixed, lowercase hex,
+/// zero-padded to 64 digits. They deserialize leniently from strings, read like `Proof::inputs`,
+/// and from JSON integers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PublicInputs<T>(pub Vec<T>);
+
+impl<T: Field> PublicInputs<T> {
+    /// Parses a single input, see `parse_field_input` for the accepted encodings
+    pub fn parse(s: &str) -> Result<T, String> {
+        parse_field_input(s).map_err(|reason| format!("invalid public input `{}`: {}", s, reason))
+    }
+
+    pub fn from_strings<S: AsRef<str>>(inputs: &[S]) -> Result<Self, String> {
+        inputs
+            .iter()
+            .map(|i| Self::parse(i.as_ref()))
+            .collect::<Result<_, _>>()
+            .map(PublicInputs)
+    }
+
+    /// Returns the canonical encodings of the inputs, as written to `Proof::inputs`
+    pub fn to_strings(&self) -> Vec<String> {
+        self.0
+            .iter()
+            .map(|i| format!("0x{:0>64}", i.to_biguint().to_str_radix(16)))
+            .collect()
+    }
+}
+
+impl<T: Field> Serialize for PublicInputs<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_strings().serialize(serializer)
+    }
+}
+
+impl<'de, T: Field> Deserialize<'de> for PublicInputs<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Input {
+            String(String),
+            Unsigned(u64),
+            Signed(i64),
+        }
+
+        Vec::<Input>::deserialize(deserializer)?
+            .into_iter()
+            .map(|input| match input {
+                Input::String(s) => PublicInputs::<T>::parse(&s),
+                Input::Unsigned(n) => PublicInputs::<T>::parse(&n.to_string()),
+                Input::Signed(n) => PublicInputs::<T>::parse(&n.to_string()),
+            })
+            .collect::<Result<_, _>>()
+            .map(PublicInputs)
+            .map_err(D::Error::custom)
+    }
+}
+
+impl<P> Proof<P> {
+    /// Returns the public inputs of the proof as elements of `T`
+    pub fn public_inputs<T: Field>(&self) -> Result<PublicInputs<T>, String> {
+        PublicInputs::from_strings(&self.inputs)
+    }
+}
+
+fn deserialize_inputs<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    Vec::<InputEncoding>::deserialize(deserializer)?
+        .into_iter()
+        .map(|input| match input {
+            InputEncoding::Flat(s) => Ok(s),
+            InputEncoding::Limbs(limbs) => limbs_to_hex(&limbs).map_err(D::Error::custom),
+        })
+        .collect()
 }
 
 impl<T: Serialize + DeserializeOwned> Proof<T> {
     fn new(proof: T, inputs: Vec<String>, raw: String) -> Self {
-        Proof { proof, inputs, raw }
+        Proof {
+            proof,
+            inputs,
+            raw,
+            producer_version: Some(ZOKRATES_VERSION.to_string()),
+        }
+    }
+
+    /// Serializes the proof as indented JSON. Keys are written in the order of the fields,
+    /// so that the output of equal proofs is identical
+    pub fn to_json_pretty(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    /// Serializes the proof as JSON without any whitespace, with keys ordered like `to_json_pretty`
+    pub fn to_json_compact(&self) -> String {
+        serde_json::to_string(self).unwrap()
     }
 }
 
-#[derive(Serialize, Deserialize)]
+/// A point of G1 as a pair of hex encoded coordinates, which are normalized when deserialized,
+/// see `G1Affine::normalize`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+#[serde(from = "(String, String)")]
 pub struct G1Affine(String, String);
 
-#[derive(Serialize, Deserialize)]
+/// A point of G2 as a pair of coordinates in the quadratic extension field, see `in_math_order`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+#[serde(from = "(G1Affine, G1Affine)")]
 pub struct G2Affine(G1Affine, G1Affine);
 
+/// Returns the canonical form of a hex encoded coordinate: `0x`-prefixed, lowercase and zero-padded
+/// to 64 digits, or to the next multiple of 32 digits for larger values, which is the width of the
+/// coordinates of all supported curves. Returns `None` if `s` is not hex encoded.
+fn normalize_hex(s: &str) -> Option<String> {
+    let digits = s
+        .strip_prefix("0x")
+        .or_else(|| s.strip_prefix("0X"))
+        .unwrap_or(s);
+
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    let digits = digits.trim_start_matches('0').to_lowercase();
+    let width = std::cmp::max(64, (digits.len() + 31) / 32 * 32);
+
+    Some(format!("0x{:0>width$}", digits, width = width))
+}
+
+impl G1Affine {
+    /// Creates a point with normalized coordinates, see `normalize`
+    pub fn new(x: String, y: String) -> Self {
+        let mut point = G1Affine(x, y);
+        point.normalize();
+        point
+    }
+
+    /// Rewrites the hex encoded coordinates to `0x`-prefixed, lowercase, zero-padded hex, so that
+    /// the encodings of a point compare equal. Coordinates which are not hex encoded are kept.
+    pub fn normalize(&mut self) {
+        for coordinate in &mut [&mut self.0, &mut self.1] {
+            if let Some(normalized) = normalize_hex(coordinate) {
+                **coordinate = normalized;
+            }
+        }
+    }
+}
+
+impl G2Affine {
+    /// Creates a point with normalized coordinates, see `normalize`
+    pub fn new(x: G1Affine, y: G1Affine) -> Self {
+        let mut point = G2Affine(x, y);
+        point.normalize();
+        point
+    }
+
+    /// Normalizes the components of both coordinates, see `G1Affine::normalize`
+    pub fn normalize(&mut self) {
+        self.0.normalize();
+        self.1.normalize();
+    }
+
+    /// Returns this point with the components of each coordinate in math order.
+    ///
+    /// Each coordinate of a G2 point is an element `c0 + c1 * u` of the quadratic extension field.
+    /// In math order, a coordinate is written `(c0, c1)`. ZoKrates keys and proofs, like the
+    /// Ethereum precompiles and the exported verifier, write it `(c1, c0)`.
+    pub fn in_math_order(&self) -> G2Affine {
+        G2Affine(
+            G1Affine((self.0).1.clone(), (self.0).0.clone()),
+            G1Affine((self.1).1.clone(), (self.1).0.clone()),
+        )
+    }
+
+    /// Returns the point in ZoKrates order from a point whose coordinates are in math order,
+    /// see `in_math_order`
+    pub fn from_math_order(point: &G2Affine) -> G2Affine {
+        // swapping the components is its own inverse
+        point.in_math_order()
+    }
+}
+
+impl From<(String, String)> for G1Affine {
+    fn from((x, y): (String, String)) -> Self {
+        G1Affine::new(x, y)
+    }
+}
+
+impl From<(G1Affine, G1Affine)> for G2Affine {
+    fn from((x, y): (G1Affine, G1Affine)) -> Self {
+        G2Affine::new(x, y)
+    }
+}
+
 impl ToString for G1Affine {
     fn to_string(&self) -> String {
         format!("{}, {}", self.0, self.1)
@@ -69,6 +504,89 @@ impl ToString for G2Affine {
     }
 }
 
+/// The number of rounds of `hashes/mimc7/mimc7R90` in the standard library
+const MIMC7_ROUNDS: usize = 90;
+
+/// The round constants of `hashes/mimc7/mimc7R90` in the standard library, the first ones of
+/// `hashes/mimc7/constants`: zero, followed by the iterated keccak256 hashes of the seed `mimc`
+/// reduced modulo the scalar field of bn128
+fn mimc7_constants<T: Field>() -> Vec<T> {
+    let mut constants = vec![T::zero()];
+    let mut c = Keccak256::digest(b"mimc");
+    for _ in 1..MIMC7_ROUNDS {
+        c = Keccak256::digest(&c);
+        constants.push(T::from(BigUint::from_bytes_be(&c) % Bn128Field::modulus()));
+    }
+    constants
+}
+
+/// The MiMC7 hash of `x` with key `k`, computed like `hashes/mimc7/mimc7R90` in the standard library
+fn mimc7<T: Field>(constants: &[T], x: T, k: T) -> T {
+    let mut t = x;
+    for c in constants {
+        let u = t + &k + c;
+        let u2 = u.clone() * &u;
+        let u4 = u2.clone() * &u2;
+        t = u4 * &u2 * &u;
+    }
+    t + &k
+}
+
+/// Packs many values into a single public input, so that the on-chain verification does one
+/// scalar multiplication instead of one per value.
+///
+/// The values are hashed with the MiMC7 construction of `hashes/mimc7/mimc7R90`, chaining the
+/// result as the key of the next value, which a program reproduces with:
+///
+/// ```zokrates
+/// field packed = 0
+/// for field i in 0..N do
+///     packed = packed + values[i] + mimc7R90(values[i], packed)
+/// endfor
+/// ```
+///
+/// The program then returns `packed` as its only public output, see `G16::verify_packed`.
+pub fn pack_public_inputs<T: Field>(inputs: &[T]) -> T {
+    let constants = mimc7_constants::<T>();
+    inputs.iter().fold(T::zero(), |packed, input| {
+        let hash = mimc7(&constants, input.clone(), packed.clone());
+        packed + input + hash
+    })
+}
+
+/// The version of `zokrates_core`, recorded as the producer version of the proofs and keys it
+/// generates
+pub const ZOKRATES_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Checks that a proof or a key generated by ZoKrates `producer_version` is supported by this
+/// version. Proofs and keys of the same or an older version are, as are those which do not record
+/// a version. Those of a newer major version are not, where following semver, a newer minor version
+/// is a newer major version as long as the major version is 0.
+pub fn check_producer_version(producer_version: Option<&str>) -> Result<(), String> {
+    let producer_version = match producer_version {
+        Some(v) => v,
+        None => return Ok(()),
+    };
+
+    let major = |version: &str| -> Option<(u64, u64)> {
+        let mut parts = version.split('.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next()?.parse().ok()?;
+        Some(if major == 0 { (0, minor) } else { (major, 0) })
+    };
+
+    let producer = major(producer_version)
+        .ok_or_else(|| format!("invalid producer version `{}`", producer_version))?;
+
+    if producer > major(ZOKRATES_VERSION).unwrap() {
+        return Err(format!(
+            "generated by ZoKrates {}, which is newer than the supported version {}",
+            producer_version, ZOKRATES_VERSION
+        ));
+    }
+
+    Ok(())
+}
 
 pub trait ProofSystem<T: Field>
 where
@@ -90,3 +608,331 @@ where
 
     fn verify(vk: Self::VerificationKey, proof: Proof<Self::ProofPoints>) -> bool;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    fn hash<H: Hash>(value: &H) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn proof_with_inputs(inputs: serde_json::Value) -> serde_json::Result<Proof<()>> {
+        serde_json::from_value(serde_json::json!({
+            "proof": null,
+            "inputs": inputs,
+            "raw": ""
+        }))
+    }
+
+    #[test]
+    fn setup_bundle() {
+        use crate::ir::Prog;
+        use crate::proof_system::bellman::groth16::{VerificationKey, G16};
+
+        let program = identity_program();
+
+        let bundle = SetupBundle::new(&program, G16::setup(program.clone()));
+
+        let mut written = vec![];
+        bundle.write(&mut written).unwrap();
+        assert!(written.starts_with(b"ZKSB"));
+
+        let read = SetupBundle::<VerificationKey>::read(&written[..]).unwrap();
+        assert_eq!(read, bundle);
+        assert_eq!(read.program_hash, program_hash(&program));
+        assert_eq!(read.check_program(&program), Ok(()));
+
+        let other = Prog {
+            private: vec![true],
+            ..program
+        };
+        assert!(read.check_program(&other).is_err());
+
+        assert_eq!(
+            SetupBundle::<VerificationKey>::read(&written[..written.len() - 1]),
+            Err(String::from("Unexpected end of proving key"))
+        );
+        assert_eq!(
+            SetupBundle::<VerificationKey>::read(&b"ZOK\0"[..]),
+            Err(String::from("Wrong magic number"))
+        );
+    }
+
+    #[test]
+    fn self_test() {
+        assert_eq!(super::self_test(), Ok(()));
+    }
+
+    #[test]
+    fn check_producer_version() {
+        let (major, minor) = {
+            let mut parts = ZOKRATES_VERSION
+                .split('.')
+                .map(|p| p.parse::<u64>().unwrap());
+            (parts.next().unwrap(), parts.next().unwrap())
+        };
+
+        // same version
+        assert!(super::check_producer_version(Some(ZOKRATES_VERSION)).is_ok());
+        // no version
+        assert!(super::check_producer_version(None).is_ok());
+        // older versions
+        assert!(super::check_producer_version(Some("0.0.1")).is_ok());
+        assert!(super::check_producer_version(Some(&format!("{}.{}.999", major, minor))).is_ok());
+        // newer major version
+        assert!(super::check_producer_version(Some(&format!("{}.0.0", major + 1))).is_err());
+        if major == 0 {
+            assert!(super::check_producer_version(Some(&format!("0.{}.0", minor + 1))).is_err());
+        }
+
+        assert_eq!(
+            super::check_producer_version(Some("latest")),
+            Err(String::from("invalid producer version `latest`"))
+        );
+    }
+
+    #[test]
+    fn public_inputs() {
+        let modulus = Bn128Field::modulus();
+
+        let parsed: PublicInputs<Bn128Field> = serde_json::from_value(serde_json::json!([
+            format!("0x{:064x}", 42),
+            "0x2a",
+            "42",
+            42,
+            "-1",
+            "-0x1",
+            -1,
+            "0"
+        ]))
+        .unwrap();
+
+        let minus_one = Bn128Field::max_value();
+        assert_eq!(
+            parsed,
+            PublicInputs(vec![
+                42.into(),
+                42.into(),
+                42.into(),
+                42.into(),
+                minus_one.clone(),
+                minus_one.clone(),
+                minus_one.clone(),
+                0.into()
+            ])
+        );
+
+        // inputs always serialize canonically
+        assert_eq!(
+            serde_json::to_value(&PublicInputs(vec![Bn128Field::from(42), minus_one])).unwrap(),
+            serde_json::json!([
+                format!("0x{:064x}", 42),
+                format!("0x{}", (modulus.clone() - 1u32).to_str_radix(16))
+            ])
+        );
+        let reparsed: PublicInputs<Bn128Field> =
+            serde_json::from_value(serde_json::to_value(&parsed).unwrap()).unwrap();
+        assert_eq!(reparsed, parsed);
+
+        // the modulus and values which are not numbers are rejected
+        for invalid in vec![
+            serde_json::json!([modulus.to_string()]),
+            serde_json::json!([format!("-{}", modulus)]),
+            serde_json::json!(["0xg"]),
+            serde_json::json!(["2a"]),
+            serde_json::json!([""]),
+            serde_json::json!([1.5]),
+        ] {
+            assert!(serde_json::from_value::<PublicInputs<Bn128Field>>(invalid).is_err());
+        }
+
+        // the inputs of a proof
+        let proof =
+            proof_with_inputs(serde_json::json!([format!("0x{:064x}", 42), "-0x1"])).unwrap();
+        assert_eq!(
+            proof.public_inputs::<Bn128Field>(),
+            Ok(PublicInputs(vec![42.into(), Bn128Field::max_value()]))
+        );
+    }
+
+    #[test]
+    fn deserialize_limb_inputs() {
+        let flat = proof_with_inputs(serde_json::json!([
+            format!("0x{:064x}", 42),
+            format!("0x{:032x}{:016x}{:016x}", 3, 2, 1)
+        ]))
+        .unwrap();
+
+        let limbs = proof_with_inputs(serde_json::json!([[42, 0, 0, 0], ["1", "0x2", 3]])).unwrap();
+
+        assert_eq!(flat, limbs);
+
+        let parsed = |proof: &Proof<()>| {
+            proof
+                .inputs
+                .iter()
+                .map(|i| Bn128Field::try_from_str(i.trim_start_matches("0x"), 16).unwrap())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            parsed(&limbs),
+            vec![
+                Bn128Field::from(42),
+                Bn128Field::from(
+                    BigUint::from(1u32)
+                        + (BigUint::from(2u32) << 64)
+                        + (BigUint::from(3u32) << 128)
+                )
+            ]
+        );
+
+        // both encodings can be mixed, and serialization always writes flat strings
+        let mixed = proof_with_inputs(serde_json::json!([[42], flat.inputs[1]])).unwrap();
+        assert_eq!(mixed, flat);
+        assert_eq!(
+            serde_json::to_value(&mixed).unwrap()["inputs"],
+            serde_json::to_value(&flat.inputs).unwrap()
+        );
+
+        assert!(proof_with_inputs(serde_json::json!([["0x1ffffffffffffffff"]])).is_err());
+        assert!(proof_with_inputs(serde_json::json!([[-1]])).is_err());
+        assert!(proof_with_inputs(serde_json::json!([42])).is_err());
+    }
+
+    #[test]
+    fn mimc7() {
+        // the test vectors of `hashes/mimc7/mimc7R90` in the standard library
+        let constants = mimc7_constants::<Bn128Field>();
+        assert_eq!(constants.len(), 90);
+        assert_eq!(
+            constants[1],
+            Bn128Field::try_from_dec_str(
+                "20888961410941983456478427210666206549300505294776164667214940546594746570981"
+            )
+            .unwrap()
+        );
+
+        let hash = |x: &str, k: &str| {
+            super::mimc7(
+                &constants,
+                Bn128Field::try_from_dec_str(x).unwrap(),
+                Bn128Field::try_from_dec_str(k).unwrap(),
+            )
+            .to_dec_string()
+        };
+        assert_eq!(
+            hash("0", "0"),
+            "20281265111705407344053532742843085357648991805359414661661476832595822221514"
+        );
+        assert_eq!(
+            hash("100", "0"),
+            "1010054095264022068840870550831559811104631937745987065544478027572003292636"
+        );
+        assert_eq!(
+            hash(
+                "21888242871839275222246405745257275088548364400416034343698204186575808495618",
+                "1"
+            ),
+            "8189519586469873426687580455476035992041353456517724932462363814215190642760"
+        );
+    }
+
+    #[test]
+    fn pack_public_inputs() {
+        let samples: Vec<Vec<Bn128Field>> = vec![
+            vec![],
+            vec![0.into()],
+            vec![0.into(), 0.into()],
+            vec![1.into()],
+            vec![1.into(), 2.into()],
+            vec![2.into(), 1.into()],
+            vec![1.into(), 2.into(), 3.into()],
+            vec![Bn128Field::max_value()],
+        ];
+
+        let packed: Vec<Bn128Field> = samples
+            .iter()
+            .map(|s| super::pack_public_inputs(s))
+            .collect();
+
+        // deterministic
+        assert_eq!(
+            packed,
+            samples
+                .iter()
+                .map(|s| super::pack_public_inputs(s))
+                .collect::<Vec<_>>()
+        );
+
+        // no collisions, neither between orders nor between lengths
+        for i in 0..packed.len() {
+            for j in 0..i {
+                assert_ne!(packed[i], packed[j], "{:?} {:?}", samples[i], samples[j]);
+            }
+        }
+    }
+
+    #[test]
+    fn normalize() {
+        let upper = G1Affine::new(String::from("0xABCD"), String::from("0X00EF"));
+        let lower = G1Affine::new(String::from("0xabcd"), String::from("ef"));
+
+        assert_eq!(upper, lower);
+        assert_eq!(hash(&upper), hash(&lower));
+        assert_eq!(upper.0, format!("0x{:064x}", 0xabcd));
+        assert_eq!(upper.1, format!("0x{:064x}", 0xef));
+
+        let g2 = |x: &G1Affine| G2Affine::new(x.clone(), x.clone());
+        assert_eq!(g2(&upper), g2(&lower));
+        assert_eq!(hash(&g2(&upper)), hash(&g2(&lower)));
+
+        // points built without `new` are normalized in place
+        let mut raw = G1Affine(String::from("0xABCD"), String::from("0xEF"));
+        assert_ne!(raw, lower);
+        raw.normalize();
+        assert_eq!(raw, lower);
+    }
+
+    #[test]
+    fn normalize_when_deserialized() {
+        let lower = G1Affine::new(String::from("0xabcd"), String::from("0xef"));
+
+        let g1: G1Affine = serde_json::from_str(r#"["0xABCD", "0X00EF"]"#).unwrap();
+        assert_eq!(g1, lower);
+        assert_eq!(hash(&g1), hash(&lower));
+
+        let g2: G2Affine =
+            serde_json::from_str(r#"[["0xABCD", "0X00EF"], ["abcd", "0xef"]]"#).unwrap();
+        assert_eq!(g2, G2Affine::new(lower.clone(), lower.clone()));
+
+        // a round trip keeps the normalized encoding
+        let json = serde_json::to_string(&g2).unwrap();
+        assert_eq!(serde_json::from_str::<G2Affine>(&json).unwrap(), g2);
+        assert_eq!(
+            json,
+            serde_json::to_string(&G2Affine::new(lower.clone(), lower)).unwrap()
+        );
+    }
+
+    #[test]
+    fn normalize_wide_coordinates() {
+        // coordinates of bls12_381 are 48 bytes wide
+        let wide = format!("0x1{}", "0".repeat(95));
+        let mut point = G1Affine(wide.to_uppercase().replace("0X", "0x"), String::from("0x1"));
+        point.normalize();
+        assert_eq!(point.0, wide);
+        assert_eq!(point.1.len(), 66);
+    }
+
+    #[test]
+    fn normalize_keeps_invalid_coordinates() {
+        let point = G1Affine::new(String::from("VK_GAMMA_ABC_0_X"), String::from("0x"));
+        assert_eq!(point.0, "VK_GAMMA_ABC_0_X");
+        assert_eq!(point.1, "0x");
+    }
+}
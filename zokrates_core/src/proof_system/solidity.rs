@@ -1,3 +1,118 @@
+use regex::Regex;
+
+/// The names of the placeholders, written `<%name%>` in a template, which are filled when
+/// exporting a G16 verifier
+pub(crate) mod placeholder {
+    pub const CONTRACT_NAME: &str = "contract_name";
+    pub const VERIFY_MODIFIERS: &str = "verify_modifiers";
+    pub const VERIFY_TX_MODIFIERS: &str = "verify_tx_modifiers";
+    pub const VERIFIED_EVENT_DECLARATION: &str = "verified_event_declaration";
+    pub const VERIFIED_EVENT_EMIT: &str = "verified_event_emit";
+    pub const VERIFYING_KEY_HASH: &str = "verifying_key_hash";
+    pub const PUBLIC_INPUT_HASH_WRAPPER: &str = "public_input_hash_wrapper";
+    pub const INPUT_COUNT_MESSAGE: &str = "input_count_message";
+    pub const INPUT_RANGE_MESSAGE: &str = "input_range_message";
+    pub const GAMMA_ABC_CONSTANTS: &str = "gamma_abc_constants";
+    pub const VK_ALPHA: &str = "vk_alpha";
+    pub const VK_BETA: &str = "vk_beta";
+    pub const VK_GAMMA: &str = "vk_gamma";
+    pub const VK_DELTA: &str = "vk_delta";
+    pub const VK_GAMMA_ABC_LENGTH: &str = "vk_gamma_abc_length";
+    pub const VK_GAMMA_ABC_PTS: &str = "vk_gamma_abc_pts";
+    pub const VK_INPUT_LENGTH: &str = "vk_input_length";
+    pub const VK_BRANCHES: &str = "vk_branches";
+    pub const ARGUMENT_LOCATION: &str = "argument_location";
+    pub const INPUT_LOOP: &str = "input_loop";
+    pub const INPUT_ARGUMENT: &str = "input_argument";
+    pub const SCALAR_FIELD_MODULUS: &str = "scalar_field_modulus";
+
+    // only in the Cairo verifier template
+    #[cfg(feature = "cairo")]
+    pub const VK_CONSTANTS: &str = "vk_constants";
+    #[cfg(feature = "cairo")]
+    pub const VK_GAMMA_ABC_0: &str = "vk_gamma_abc_0";
+    #[cfg(feature = "cairo")]
+    pub const VK_X_TERMS: &str = "vk_x_terms";
+    #[cfg(feature = "cairo")]
+    pub const SCALAR_FIELD_MODULUS_LOW: &str = "scalar_field_modulus_low";
+    #[cfg(feature = "cairo")]
+    pub const SCALAR_FIELD_MODULUS_HIGH: &str = "scalar_field_modulus_high";
+}
+
+/// The placeholders of the Solidity verifier templates, see `template_placeholders`
+const TEMPLATE_PLACEHOLDERS: &[&str] = &[
+    placeholder::CONTRACT_NAME,
+    placeholder::VERIFY_MODIFIERS,
+    placeholder::VERIFY_TX_MODIFIERS,
+    placeholder::VERIFIED_EVENT_DECLARATION,
+    placeholder::VERIFIED_EVENT_EMIT,
+    placeholder::VERIFYING_KEY_HASH,
+    placeholder::PUBLIC_INPUT_HASH_WRAPPER,
+    placeholder::INPUT_COUNT_MESSAGE,
+    placeholder::INPUT_RANGE_MESSAGE,
+    placeholder::GAMMA_ABC_CONSTANTS,
+    placeholder::VK_ALPHA,
+    placeholder::VK_BETA,
+    placeholder::VK_GAMMA,
+    placeholder::VK_DELTA,
+    placeholder::VK_GAMMA_ABC_LENGTH,
+    placeholder::VK_GAMMA_ABC_PTS,
+    placeholder::VK_INPUT_LENGTH,
+    placeholder::VK_BRANCHES,
+    placeholder::ARGUMENT_LOCATION,
+    placeholder::INPUT_LOOP,
+    placeholder::INPUT_ARGUMENT,
+    placeholder::SCALAR_FIELD_MODULUS,
+];
+
+/// The placeholders of the points of the verification key. A template must contain all of them,
+/// unless it is a multi-verifier template, whose `<%vk_branches%>` is filled with one branch per
+/// key which contains them.
+const KEY_PLACEHOLDERS: &[&str] = &[
+    placeholder::VK_ALPHA,
+    placeholder::VK_BETA,
+    placeholder::VK_GAMMA,
+    placeholder::VK_DELTA,
+    placeholder::VK_GAMMA_ABC_LENGTH,
+    placeholder::VK_GAMMA_ABC_PTS,
+];
+
+/// Replaces every occurrence of the placeholder `name` in `template` with `value`
+pub(crate) fn fill(template: &str, name: &str, value: &str) -> String {
+    template.replace(&format!("<%{}%>", name), value)
+}
+
+/// Returns the names of the placeholders which are filled in a Solidity verifier template, see
+/// `validate_template`
+pub fn template_placeholders() -> &'static [&'static str] {
+    TEMPLATE_PLACEHOLDERS
+}
+
+/// Checks that a custom verifier template only uses placeholders of `template_placeholders`, and
+/// that it holds the verification key: either it contains the placeholders of all points of the
+/// key, or it is a multi-verifier template with `<%vk_branches%>`
+pub fn validate_template(template: &str) -> Result<(), String> {
+    let placeholder_regex = Regex::new(r"<%([^%]*)%>").unwrap();
+
+    let used: Vec<&str> = placeholder_regex
+        .captures_iter(template)
+        .map(|c| c.get(1).unwrap().as_str())
+        .collect();
+
+    if let Some(unknown) = used.iter().find(|p| !TEMPLATE_PLACEHOLDERS.contains(*p)) {
+        return Err(format!("Unknown placeholder `<%{}%>`", unknown));
+    }
+
+    if used.contains(&placeholder::VK_BRANCHES) {
+        return Ok(());
+    }
+
+    match KEY_PLACEHOLDERS.iter().find(|p| !used.contains(*p)) {
+        Some(missing) => Err(format!("Missing placeholder `<%{}%>`", missing)),
+        None => Ok(()),
+    }
+}
+
 pub const SOLIDITY_G2_ADDITION_LIB: &str = r#"// This file is LGPL3 Licensed
 pragma solidity ^0.6.1;
 
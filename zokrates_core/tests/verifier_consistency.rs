@@ -0,0 +1,159 @@
+extern crate hex;
+extern crate pairing_ce;
+extern crate serde_json;
+extern crate zokrates_core;
+extern crate zokrates_field;
+
+use pairing_ce::ff::Field as PairingField;
+use pairing_ce::{to_hex, CurveAffine, CurveProjective, Engine};
+use zokrates_core::flat_absy::FlatVariable;
+use zokrates_core::ir::{Function, Interpreter, Prog, Statement};
+use zokrates_core::proof_system::bellman::groth16::G16;
+use zokrates_core::proof_system::{ProofSystem, SolidityAbi};
+use zokrates_field::{Bn128Field, Field};
+
+type Bn256 = <Bn128Field as Field>::BellmanEngine;
+
+/// The `0x`-prefixed hex words in `source` between `start` and `end`, with the `uint256(...)`
+/// conversion of the exported verifier removed
+fn words_between(source: &str, start: &str, end: &str) -> Vec<String> {
+    let start = source.find(start).unwrap();
+    let end = start + source[start..].find(end).unwrap();
+
+    source[start..end]
+        .split("uint256(")
+        .skip(1)
+        .map(|s| s[..s.find(')').unwrap()].to_string())
+        .collect()
+}
+
+/// Runs the setup and a proof for `program` on `arguments`, verifies the proof in Rust and checks
+/// that the exported Solidity verifiers would compute the same verification: the same scalar field,
+/// the same number and order of public inputs, the same key in the order of the precompiles, and a
+/// pairing check which accepts the proof when evaluated in Rust
+fn assert_verifiers_agree(program: Prog<Bn128Field>, arguments: Vec<Bn128Field>) {
+    let keypair = G16::setup(program.clone());
+    let witness = Interpreter::default()
+        .execute(&program, &arguments)
+        .unwrap();
+    let proof = G16::generate_proof(program.clone(), witness, keypair.pk);
+
+    assert!(<G16 as ProofSystem<Bn128Field>>::verify(
+        keypair.vk.clone(),
+        proof.clone()
+    ));
+
+    let inputs: Vec<Bn128Field> = proof
+        .inputs
+        .iter()
+        .map(|i| Bn128Field::try_from_str(i.trim_start_matches("0x"), 16).unwrap())
+        .collect();
+    let input_count = program.public_input_layout().len();
+    assert_eq!(inputs.len(), input_count);
+    assert_eq!(keypair.vk.expected_public_input_count(), input_count);
+
+    let key_words: Vec<String> = keypair
+        .vk
+        .to_eth_words()
+        .iter()
+        .map(|w| format!("0x{}", hex::encode(w)))
+        .collect();
+
+    for abi in vec![SolidityAbi::V1, SolidityAbi::V2] {
+        let source =
+            <G16 as ProofSystem<Bn128Field>>::export_solidity_verifier(keypair.vk.clone(), abi);
+
+        // the inputs are checked against the scalar field of the curve
+        assert!(source.contains(&format!(
+            "uint256 snark_scalar_field = {};",
+            Bn128Field::max_value().to_biguint() + 1u32
+        )));
+        assert!(inputs
+            .iter()
+            .all(|i| i.to_biguint() < Bn128Field::max_value().to_biguint() + 1u32));
+
+        // one `gamma_abc` point per input, plus the constant term
+        assert!(source.contains(&format!("uint[{}] memory input", input_count)));
+        assert!(source.contains(&format!(
+            "vk.gamma_abc = new Pairing.G1Point[]({});",
+            input_count + 1
+        )));
+
+        // the key is embedded in the layout of `to_eth_words`
+        assert_eq!(
+            words_between(&source, "function verifyingKey()", "function verify("),
+            key_words
+        );
+
+        // the pairing check of the verifier
+        assert!(source.contains(
+            "Pairing.pairingProd4(
+             proof.a, proof.b,
+             Pairing.negate(vk_x), vk.gamma,
+             Pairing.negate(proof.c), vk.delta,
+             Pairing.negate(vk.alpha), vk.beta)"
+        ));
+    }
+
+    let vk = keypair.vk.to_bellman::<Bn128Field>().unwrap();
+    let points = proof.proof.to_bellman::<Bn128Field>().unwrap();
+
+    // the precompiles take the imaginary component of a G2 coordinate first
+    let (beta_x, beta_y) = vk.beta_g2.into_xy_unchecked();
+    assert_eq!(
+        key_words[2..6].to_vec(),
+        vec![&beta_x.c1, &beta_x.c0, &beta_y.c1, &beta_y.c0]
+            .into_iter()
+            .map(|c| format!("0x{}", to_hex(c)))
+            .collect::<Vec<_>>()
+    );
+
+    // vk_x as computed by the loop of `verify`
+    let mut vk_x = <Bn256 as Engine>::G1::zero();
+    for (input, point) in inputs.iter().zip(vk.ic.iter().skip(1)) {
+        vk_x.add_assign(&point.mul(input.clone().into_bellman()));
+    }
+    vk_x.add_assign_mixed(&vk.ic[0]);
+    let vk_x = vk_x.into_affine();
+
+    let (x, y) = vk_x.into_xy_unchecked();
+    assert_eq!(
+        serde_json::to_value(keypair.vk.compute_vk_x(&inputs)).unwrap(),
+        serde_json::json!([format!("0x{}", to_hex(&x)), format!("0x{}", to_hex(&y))])
+    );
+
+    // `pairingProd4` accepts if the product of the pairings is one
+    let negate = |mut p: <Bn256 as Engine>::G1Affine| {
+        p.negate();
+        p
+    };
+    let pairs = vec![
+        (points.a.prepare(), points.b.prepare()),
+        (negate(vk_x).prepare(), vk.gamma_g2.prepare()),
+        (negate(points.c).prepare(), vk.delta_g2.prepare()),
+        (negate(vk.alpha_g1).prepare(), vk.beta_g2.prepare()),
+    ];
+    let product = Bn256::final_exponentiation(&Bn256::miller_loop(
+        pairs.iter().map(|(a, b)| (a, b)).collect::<Vec<_>>().iter(),
+    ))
+    .unwrap();
+    assert_eq!(product, <Bn256 as Engine>::Fqk::one());
+}
+
+#[test]
+fn sample_program() {
+    let program: Prog<Bn128Field> = Prog {
+        main: Function {
+            id: String::from("main"),
+            arguments: vec![FlatVariable::new(0)],
+            returns: vec![FlatVariable::public(0)],
+            statements: vec![Statement::Constraint(
+                FlatVariable::new(0).into(),
+                FlatVariable::public(0).into(),
+            )],
+        },
+        private: vec![false],
+    };
+
+    assert_verifiers_agree(program, vec![Bn128Field::from(42)]);
+}
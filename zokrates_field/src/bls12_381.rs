@@ -1,8 +1,10 @@
-use bellman_ce::pairing::bls12_381::{Bls12, Fq2};
+use bellman_ce::pairing::bls12_381::{Bls12, Fq12, Fq2, Fq6};
 
 prime_field!(
     b"52435875175126190479447740508185965837690552500527637822603658699938581184513",
     Bls12,
     Fq2,
+    Fq6,
+    Fq12,
     "bls12_381"
 );
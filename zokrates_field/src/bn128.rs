@@ -1,9 +1,11 @@
-use bellman_ce::pairing::bn256::{Bn256, Fq2};
+use bellman_ce::pairing::bn256::{Bn256, Fq12, Fq2, Fq6};
 
 prime_field!(
     b"21888242871839275222246405745257275088548364400416034343698204186575808495617",
     Bn256,
     Fq2,
+    Fq6,
+    Fq12,
     "bn128"
 );
 
@@ -31,6 +33,50 @@ mod tests {
             );
         }
 
+        #[test]
+        fn modulus() {
+            assert_eq!(
+                FieldPrime::modulus(),
+                FieldPrime::max_value().to_biguint() + 1u32
+            );
+        }
+
+        #[test]
+        fn curve_params() {
+            let params = FieldPrime::curve_params();
+            assert_eq!(params.name, "bn128");
+            assert_eq!(params.scalar_modulus, FieldPrime::modulus());
+            assert_eq!(
+                params.base_modulus,
+                "21888242871839275222246405745257275088696311157297823662689037894645226208583"
+                    .parse::<BigUint>()
+                    .unwrap()
+            );
+        }
+
+        #[test]
+        fn visit_field() {
+            use crate::{supported_curves, visit_field, FieldVisitor};
+
+            struct Modulus;
+
+            impl FieldVisitor for Modulus {
+                type Output = BigUint;
+
+                fn visit<T: Field>(self) -> BigUint {
+                    T::modulus()
+                }
+            }
+
+            assert_eq!(supported_curves(), &["bn128", "bls12_381"]);
+            assert_eq!(visit_field("bn128", Modulus), Some(FieldPrime::modulus()));
+            assert_eq!(
+                visit_field("bls12_381", Modulus),
+                Some(crate::Bls12Field::modulus())
+            );
+            assert_eq!(visit_field("bw6_761", Modulus), None);
+        }
+
         #[test]
         fn positive_number() {
             assert_eq!(
@@ -229,6 +275,32 @@ mod tests {
             );
         }
 
+        #[test]
+        fn pow_u64() {
+            assert_eq!(FieldPrime::from("54").pow_u64(0), FieldPrime::one());
+            assert_eq!(
+                FieldPrime::from("54").pow_u64(12),
+                FieldPrime::from("54").pow(12)
+            );
+            assert_eq!(
+                FieldPrime::from("-54").pow_u64(11),
+                FieldPrime::from("-54").pow(11)
+            );
+            // exponents which are too large for repeated multiplication
+            assert_eq!(
+                FieldPrime::from("54").pow_u64(1 << 40),
+                (0..40).fold(FieldPrime::from("54"), |acc, _| acc.clone() * &acc)
+            );
+        }
+
+        #[test]
+        fn inverse() {
+            let x = FieldPrime::from("54");
+            assert_eq!(x.clone() * x.inverse().unwrap(), FieldPrime::one());
+            assert_eq!(x.inverse().unwrap(), x.inverse_mul());
+            assert_eq!(FieldPrime::from("0").inverse(), None);
+        }
+
         #[test]
         fn pow() {
             assert_eq!(
@@ -278,6 +350,28 @@ mod tests {
             assert_eq!(fp, FieldPrime::from_byte_vector(bv));
         }
 
+        #[test]
+        fn be_bytes_32_ser_deser() {
+            let fp = FieldPrime::from("101");
+            let bytes = fp.to_be_bytes_32();
+            assert_eq!(bytes[31], 101);
+            assert!(bytes[..31].iter().all(|b| *b == 0));
+            assert_eq!(fp, FieldPrime::from_be_bytes_32(&bytes).unwrap());
+
+            let max = FieldPrime::max_value();
+            assert_eq!(
+                max,
+                FieldPrime::from_be_bytes_32(&max.to_be_bytes_32()).unwrap()
+            );
+
+            // the modulus and larger values are rejected rather than reduced
+            let mut modulus = [0u8; 32];
+            let modulus_bytes = FieldPrime::modulus().to_bytes_be();
+            modulus[32 - modulus_bytes.len()..].copy_from_slice(&modulus_bytes);
+            assert_eq!(FieldPrime::from_be_bytes_32(&modulus), Err(()));
+            assert_eq!(FieldPrime::from_be_bytes_32(&[0xff; 32]), Err(()));
+        }
+
         #[test]
         fn dec_string_ser_deser() {
             let fp = FieldPrime::from("101");
@@ -443,5 +537,22 @@ mod tests {
 
             assert_eq!(FieldPrime::from_bellman(a), cc);
         }
+
+        #[test]
+        fn fqk_to_bytes_to_fqk() {
+            let rng = &mut thread_rng();
+            for _ in 0..10 {
+                let a: Fq12 = rng.gen();
+                let bytes = FieldPrime::fqk_to_bytes(&a);
+                assert_eq!(bytes.len(), 12 * 32);
+                assert_eq!(FieldPrime::fqk_from_bytes(&bytes), Ok(a));
+
+                assert!(FieldPrime::fqk_from_bytes(&bytes[1..]).is_err());
+                assert!(FieldPrime::fqk_from_bytes(&[&bytes[..], &[0]].concat()).is_err());
+            }
+
+            // coefficients which are not reduced
+            assert!(FieldPrime::fqk_from_bytes(&[0xff; 12 * 32]).is_err());
+        }
     }
 }
@@ -19,6 +19,17 @@ pub trait Pow<RHS> {
     fn pow(self, _: RHS) -> Self::Output;
 }
 
+/// The constants of the curve associated with a field, for instance to build the calldata of a verifier
+#[derive(Debug, Clone, PartialEq)]
+pub struct CurveParams {
+    /// the name of the curve, see `Field::name`
+    pub name: &'static str,
+    /// the modulus of the scalar field, i.e. of the values of a program and of the public inputs
+    pub scalar_modulus: BigUint,
+    /// the modulus of the base field, i.e. of the coordinates of the curve points
+    pub base_modulus: BigUint,
+}
+
 pub trait Field:
     From<i32>
     + From<u32>
@@ -69,14 +80,58 @@ pub trait Field:
 
     fn new_fq2(c0: &str, c1: &str) -> <Self::BellmanEngine as Engine>::Fqe;
 
+    /// Returns an element of the target group of the pairing as the big-endian encodings of its
+    /// base field coefficients
+    fn fqk_to_bytes(e: &<Self::BellmanEngine as Engine>::Fqk) -> Vec<u8>;
+    /// Decodes an element of the target group of the pairing encoded with `fqk_to_bytes`
+    fn fqk_from_bytes(bytes: &[u8]) -> Result<<Self::BellmanEngine as Engine>::Fqk, ()>;
+
     /// Returns this `Field`'s contents as little-endian byte vector
     fn into_byte_vector(&self) -> Vec<u8>;
     /// Returns an element of this `Field` from a little-endian byte vector
     fn from_byte_vector(_: Vec<u8>) -> Self;
+    /// Returns this `Field`'s contents as a 32-byte big-endian array, the width of a word in
+    /// Ethereum. The moduli of all supported fields are smaller than 2^256.
+    fn to_be_bytes_32(&self) -> [u8; 32] {
+        let bytes = self.to_biguint().to_bytes_be();
+        let mut res = [0u8; 32];
+        res[32 - bytes.len()..].copy_from_slice(&bytes);
+        res
+    }
+    /// Returns an element of this `Field` from a 32-byte big-endian array, or an error if the
+    /// value is not smaller than the modulus
+    fn from_be_bytes_32(bytes: &[u8; 32]) -> Result<Self, ()> {
+        let value = BigUint::from_bytes_be(bytes);
+        if value < Self::modulus() {
+            Ok(Self::from(value))
+        } else {
+            Err(())
+        }
+    }
     /// Returns this `Field`'s contents as decimal string
     fn to_dec_string(&self) -> String;
     /// Returns the multiplicative inverse, i.e.: self * self.inverse_mul() = Self::one()
     fn inverse_mul(&self) -> Self;
+    /// Returns the multiplicative inverse like `inverse_mul`, or `None` for zero which has no inverse
+    fn inverse(&self) -> Option<Self> {
+        if self.is_zero() {
+            None
+        } else {
+            Some(self.inverse_mul())
+        }
+    }
+    /// Returns `self` to the power of `exp`, using square-and-multiply rather than the `exp`
+    /// multiplications of `Pow<usize>`
+    fn pow_u64(&self, exp: u64) -> Self {
+        let mut res = Self::one();
+        for i in (0..64 - exp.leading_zeros()).rev() {
+            res = res.clone() * &res;
+            if (exp >> i) & 1 == 1 {
+                res = res * self;
+            }
+        }
+        res
+    }
     /// Returns the smallest value that can be represented by this field type.
     fn min_value() -> Self;
     /// Returns the largest value that can be represented by this field type.
@@ -117,12 +172,28 @@ pub trait Field:
     }
     /// Returns the value as a BigUint
     fn to_biguint(&self) -> BigUint;
+    /// Returns the modulus of this field, i.e. the smallest value which cannot be represented
+    fn modulus() -> BigUint;
+    /// Returns the constants of the curve associated with this field
+    fn curve_params() -> CurveParams {
+        use bellman_ce::pairing::ff::{PrimeField, PrimeFieldRepr};
+        let mut base_modulus: Vec<u8> = vec![];
+        <Self::BellmanEngine as Engine>::Fq::char()
+            .write_le(&mut base_modulus)
+            .unwrap();
+
+        CurveParams {
+            name: Self::name(),
+            scalar_modulus: Self::modulus(),
+            base_modulus: BigUint::from_bytes_le(&base_modulus),
+        }
+    }
 }
 
 #[macro_use]
 mod prime_field {
     macro_rules! prime_field {
-        ($modulus:expr, $bellman_type:ty, $fq2_type: ident, $name:expr) => {
+        ($modulus:expr, $bellman_type:ty, $fq2_type: ident, $fq6_type: ident, $fq12_type: ident, $name:expr) => {
             use crate::{Field, Pow};
             use lazy_static::lazy_static;
             use num_bigint::{BigInt, BigUint, Sign, ToBigInt};
@@ -138,6 +209,9 @@ mod prime_field {
                 static ref P: BigInt = BigInt::parse_bytes($modulus, 10).unwrap();
             }
 
+            /// the name of the curve associated with this field, see `Field::name`
+            pub const CURVE_NAME: &str = $name;
+
             #[derive(PartialEq, PartialOrd, Clone, Eq, Ord, Hash, Serialize, Deserialize)]
             pub struct FieldPrime {
                 value: BigInt,
@@ -157,10 +231,65 @@ mod prime_field {
                     }
                 }
 
+                fn fqk_to_bytes(e: &$fq12_type) -> Vec<u8> {
+                    use bellman_ce::pairing::ff::{PrimeField, PrimeFieldRepr};
+
+                    let mut res = vec![];
+                    for c in &[e.c0, e.c1] {
+                        for c in &[c.c0, c.c1, c.c2] {
+                            for c in &[c.c0, c.c1] {
+                                c.into_repr().write_be(&mut res).unwrap();
+                            }
+                        }
+                    }
+                    res
+                }
+
+                fn fqk_from_bytes(bytes: &[u8]) -> Result<$fq12_type, ()> {
+                    use bellman_ce::pairing::ff::{PrimeField, PrimeFieldRepr};
+                    type Fq = <$bellman_type as bellman_ce::pairing::Engine>::Fq;
+
+                    let mut reader = bytes;
+                    let e = {
+                        let mut fq = || -> Result<Fq, ()> {
+                            let mut repr = <Fq as PrimeField>::Repr::default();
+                            repr.read_be(&mut reader).map_err(|_| ())?;
+                            Fq::from_repr(repr).map_err(|_| ())
+                        };
+                        let mut fq2 = || -> Result<$fq2_type, ()> {
+                            Ok($fq2_type {
+                                c0: fq()?,
+                                c1: fq()?,
+                            })
+                        };
+                        let mut fq6 = || -> Result<$fq6_type, ()> {
+                            Ok($fq6_type {
+                                c0: fq2()?,
+                                c1: fq2()?,
+                                c2: fq2()?,
+                            })
+                        };
+                        $fq12_type {
+                            c0: fq6()?,
+                            c1: fq6()?,
+                        }
+                    };
+
+                    if reader.is_empty() {
+                        Ok(e)
+                    } else {
+                        Err(())
+                    }
+                }
+
                 fn to_biguint(&self) -> BigUint {
                     self.value.to_biguint().unwrap()
                 }
 
+                fn modulus() -> BigUint {
+                    P.to_biguint().unwrap()
+                }
+
                 fn into_byte_vector(&self) -> Vec<u8> {
                     match self.value.to_biguint() {
                         Option::Some(val) => val.to_bytes_le(),
@@ -238,7 +367,7 @@ mod prime_field {
                 }
 
                 fn name() -> &'static str {
-                    $name
+                    CURVE_NAME
                 }
             }
 
@@ -516,3 +645,30 @@ pub mod bn128;
 
 pub use bls12_381::FieldPrime as Bls12Field;
 pub use bn128::FieldPrime as Bn128Field;
+
+/// Code which is generic over the field, so that it can be run on the field of a curve which is
+/// only known by its name at runtime, see `visit_field`
+pub trait FieldVisitor {
+    type Output;
+    fn visit<T: Field>(self) -> Self::Output;
+}
+
+macro_rules! supported_fields {
+    ($($module:ident),*) => {
+        /// Returns the names of the curves whose field is implemented in this crate
+        pub fn supported_curves() -> &'static [&'static str] {
+            &[$($module::CURVE_NAME),*]
+        }
+
+        /// Runs `visitor` on the field of the curve named `name`, or returns `None` if that curve
+        /// is not supported
+        pub fn visit_field<V: FieldVisitor>(name: &str, visitor: V) -> Option<V::Output> {
+            match name {
+                $($module::CURVE_NAME => Some(visitor.visit::<$module::FieldPrime>()),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+supported_fields!(bn128, bls12_381);